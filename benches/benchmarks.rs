@@ -1,5 +1,9 @@
 use beta_one::{
-    games::{connect4::Connect4, tictactoe::TicTacToe, GameState},
+    games::{
+        connect4::{Connect4, Connect4Evaluator},
+        tictactoe::TicTacToe,
+        GameState,
+    },
     players::{mcts::MCTSPlayer, minimax::MinimaxPlayer, random::RandomPlayer, GamePlayer},
 };
 use criterion::{criterion_group, criterion_main, Criterion};
@@ -49,6 +53,12 @@ fn minimax_connect4_benchmark(c: &mut Criterion)
 
     group.bench_function("choose_move", |b| b.iter(|| player.choose_move(&game)));
 
+    let mut player = MinimaxPlayer::new(Some(6)).set_evaluator(Connect4Evaluator);
+    let opening = Connect4::new(6, 7, 4);
+    group.bench_function("choose_move(opening, depth 6)", |b| {
+        b.iter(|| player.choose_move(&opening))
+    });
+
     group.finish();
 }
 
@@ -56,7 +66,8 @@ fn mcts_tictactoe_benchmark(c: &mut Criterion)
 {
     let mut group = c.benchmark_group("MCTS/Tic Tac Toe");
 
-    let mut player = MCTSPlayer::new(300).set_player(RandomPlayer::from_seed(234));
+    let mut player = MCTSPlayer::new(300)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     let game = TicTacToe::new(3, 3, 3);
 
     group.bench_function("choose_move", |b| b.iter(|| player.choose_move(&game)));
@@ -68,7 +79,8 @@ fn mcts_connect4_benchmark(c: &mut Criterion)
 {
     let mut group = c.benchmark_group("MCTS/Connect 4");
 
-    let mut player = MCTSPlayer::new(300).set_player(RandomPlayer::from_seed(234));
+    let mut player = MCTSPlayer::new(300)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     let game = Connect4::new(6, 7, 4);
 
     group.bench_function("choose_move", |b| b.iter(|| player.choose_move(&game)));
@@ -82,7 +94,8 @@ fn play_tictactoe_mcts(c: &mut Criterion)
 
     let game = TicTacToe::new(3, 3, 3);
 
-    let player = MCTSPlayer::new(100).set_player(RandomPlayer::from_seed(234));
+    let player = MCTSPlayer::new(100)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     group.bench_function("play(100 iters)", |b| {
         b.iter(|| {
             game.clone()
@@ -90,7 +103,8 @@ fn play_tictactoe_mcts(c: &mut Criterion)
         })
     });
 
-    let player = MCTSPlayer::new(300).set_player(RandomPlayer::from_seed(234));
+    let player = MCTSPlayer::new(300)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     group.bench_function("play(300 iters)", |b| {
         b.iter(|| {
             game.clone()
@@ -107,14 +121,16 @@ fn play_connect4_mcts(c: &mut Criterion)
 
     let game = Connect4::new(6, 7, 4);
 
-    let player = MCTSPlayer::new(100).set_player(RandomPlayer::from_seed(234));
+    let player = MCTSPlayer::new(100)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     group.bench_function("play(100 iters)", |b| {
         b.iter(|| {
             game.clone()
                 .play(&mut player.clone(), &mut player.clone(), false)
         })
     });
-    let player = MCTSPlayer::new(300).set_player(RandomPlayer::from_seed(234));
+    let player = MCTSPlayer::new(300)
+        .set_players(RandomPlayer::from_seed(234), RandomPlayer::from_seed(235));
     group.bench_function("play(300 iters)", |b| {
         b.iter(|| {
             game.clone()
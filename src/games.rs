@@ -1,9 +1,36 @@
 use std::fmt::{self, Display, Formatter};
 
-use crate::players::GamePlayer;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    thread_rng, Rng,
+};
+
+use crate::players::{GamePlayer, SimultaneousPlayer};
+pub mod amazons;
+pub mod backgammon;
+pub mod blokus_duo;
+pub mod breakthrough;
+pub mod chess;
 pub mod connect4;
+pub mod domineering;
+pub mod dots_and_boxes;
+pub mod goofspiel;
+pub mod kalah;
+pub mod lines_of_action;
+pub mod misere;
+pub mod morris;
+pub mod nim;
+pub mod pentago;
+pub mod phantom_tictactoe;
 pub mod poker;
+pub mod quarto;
+pub mod qubic;
+pub mod samegame;
+pub mod santorini;
+pub mod sliding_puzzle;
 pub mod tictactoe;
+pub mod tron;
+pub mod ultimate_tictactoe;
 
 pub mod common;
 
@@ -21,11 +48,40 @@ pub trait GameState: Clone + Display
 
     fn check_win(&self) -> GameResult;
 
+    /// Returns the possible outcomes and their probabilities when the next move is decided by
+    /// chance (e.g. rolling dice) instead of by a player. `get_valid_moves` should return the same
+    /// moves in the same order for these states.
+    fn chance_outcomes(&self) -> Option<Vec<(Self::Move, f64)>>
+    {
+        None
+    }
+
     // TODO: possibly also return GameState instead of GameResult
     fn play(
+        self,
+        p1: &mut impl GamePlayer<Self>,
+        p2: &mut impl GamePlayer<Self>,
+        verbose: bool,
+    ) -> GameResult
+    {
+        let choose = |state: &Self| match state.player_to_move()
+        {
+            Player { id: 1 } => p1.choose_move(state),
+            Player { id: 2 } => p2.choose_move(state),
+            Player { id: n } =>
+            {
+                panic!("invalid player: {}", n)
+            },
+        };
+        self.play_with(choose, &mut thread_rng(), verbose)
+    }
+
+    /// Plays until the game is over, getting each move from `choose` unless it is decided by
+    /// chance, in which case the outcome is sampled with `rng`
+    fn play_with(
         mut self,
-        p1: &mut impl GamePlayer,
-        p2: &mut impl GamePlayer,
+        mut choose: impl FnMut(&Self) -> Self::Move,
+        rng: &mut impl Rng,
         verbose: bool,
     ) -> GameResult
     {
@@ -37,15 +93,11 @@ pub trait GameState: Clone + Display
                 println!("{}", self);
             }
 
-            // Let the current player pick their move
-            let selected_move = match self.player_to_move()
+            // Let the current player pick their move, unless it is decided by chance
+            let selected_move = match self.chance_outcomes()
             {
-                Player { id: 1 } => p1.choose_move(&self),
-                Player { id: 2 } => p2.choose_move(&self),
-                Player { id: n } =>
-                {
-                    panic!("invalid player: {}", n)
-                },
+                Some(outcomes) => outcomes[sample_outcome(&outcomes, rng)].0,
+                None => choose(&self),
             };
 
             // Print selected move
@@ -74,7 +126,12 @@ pub trait GameState: Clone + Display
         game_result
     }
 
-    fn benchmark_players(&self, p1: &mut impl GamePlayer, p2: &mut impl GamePlayer, iterations: u32)
+    fn benchmark_players(
+        &self,
+        p1: &mut impl GamePlayer<Self>,
+        p2: &mut impl GamePlayer<Self>,
+        iterations: u32,
+    )
     {
         let mut p1_wins = 0;
         let mut p2_wins = 0;
@@ -99,7 +156,7 @@ pub trait GameState: Clone + Display
                     }
                 },
                 GameResult::Draw => draws += 1,
-                GameResult::InProgress =>
+                GameResult::InProgress | GameResult::Score(_) =>
                 {},
             }
         }
@@ -107,6 +164,113 @@ pub trait GameState: Clone + Display
         println!("Games: {iterations}");
         println!("P1 Wins: {p1_wins} / Draws: {draws} / P2 Wins: {p2_wins}");
     }
+
+    /// Plays a single player game, where `player_to_move` is always player 1 and `check_win`
+    /// returns `GameResult::Score` once the game is over
+    fn play_alone(self, player: &mut impl GamePlayer<Self>, verbose: bool) -> GameResult
+    {
+        let choose = |state: &Self| {
+            assert!(
+                state.player_to_move().id == 1,
+                "a single player game should only ever have player 1 to move"
+            );
+            player.choose_move(state)
+        };
+        self.play_with(choose, &mut thread_rng(), verbose)
+    }
+
+    /// The single player version of `benchmark_players`, printing the lowest, average and
+    /// highest scores
+    fn benchmark_player(&self, player: &mut impl GamePlayer<Self>, iterations: u32)
+    {
+        let mut scores = vec![];
+        for _ in 0..iterations
+        {
+            match self.clone().play_alone(player, false)
+            {
+                GameResult::Score(score) => scores.push(score),
+                result => panic!("expected a single player game to end with a score, not {result}"),
+            }
+        }
+
+        println!("Games: {iterations}");
+        if scores.is_empty()
+        {
+            return;
+        }
+
+        let average = scores.iter().sum::<i64>() as f64 / scores.len() as f64;
+        println!(
+            "Lowest Score: {} / Average Score: {average} / Highest Score: {}",
+            scores.iter().min().unwrap(),
+            scores.iter().max().unwrap()
+        );
+    }
+}
+
+/// A two player game where both players choose their moves at the same time, and the moves are
+/// then resolved together
+pub trait SimultaneousGame: Clone + Display
+{
+    type Move: Copy + Display;
+
+    /// The moves `player` can choose from, which can't depend on what the other player chooses
+    fn get_valid_moves(&self, player: Player) -> Vec<Self::Move>;
+
+    fn do_moves(self, p1_move: Self::Move, p2_move: Self::Move) -> Self;
+
+    fn last_moves(&self) -> Option<(Self::Move, Self::Move)>;
+
+    fn check_win(&self) -> GameResult;
+
+    fn play(
+        mut self,
+        p1: &mut impl SimultaneousPlayer,
+        p2: &mut impl SimultaneousPlayer,
+        verbose: bool,
+    ) -> GameResult
+    {
+        while self.check_win() == GameResult::InProgress
+        {
+            if verbose
+            {
+                println!("{}", self);
+            }
+
+            // both players choose from the same state, so neither can see the other's move
+            let p1_move = p1.choose_simultaneous_move(&self, Player { id: 1 });
+            let p2_move = p2.choose_simultaneous_move(&self, Player { id: 2 });
+
+            if verbose
+            {
+                println!("Selected Moves: {p1_move} / {p2_move}");
+            }
+
+            self = self.do_moves(p1_move, p2_move);
+        }
+
+        if verbose
+        {
+            println!("{}", self);
+        }
+
+        let game_result = self.check_win();
+        if verbose
+        {
+            println!("{game_result}");
+        }
+
+        game_result
+    }
+}
+
+/// Picks one of the outcomes of a chance event at random, weighted by their probabilities, and
+/// returns its index
+pub fn sample_outcome<M>(outcomes: &[(M, f64)], rng: &mut impl Rng) -> usize
+{
+    let weights = WeightedIndex::new(outcomes.iter().map(|(_, p)| *p))
+        .expect("outcome probabilities should be positive");
+    weights.sample(rng)
 }
 
 #[derive(PartialEq, Eq)]
@@ -115,6 +279,8 @@ pub enum GameResult
     InProgress,
     Draw,
     Win(Player),
+    /// A single player game has finished with this score, where higher is better
+    Score(i64),
 }
 
 impl Display for GameResult
@@ -129,12 +295,13 @@ impl Display for GameResult
                 GameResult::InProgress => String::from("Game in Progress"),
                 GameResult::Draw => String::from("Draw"),
                 GameResult::Win(player) => format!("{player} Wins"),
+                GameResult::Score(score) => format!("Score: {score}"),
             }
         )
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Player
 {
     id: usize,
@@ -155,3 +322,187 @@ impl Display for Player
         write!(f, "Player {}", self.id)
     }
 }
+
+#[cfg(test)]
+mod test
+{
+    use std::fmt::{self, Display, Formatter};
+
+    use crate::players::{random::RandomPlayer, SimultaneousPlayer};
+
+    use super::{samegame::SameGame, GameResult, GameState, Player, SimultaneousGame};
+
+    // repeated matching pennies, where player 2 scores a point when the coins match and player 1
+    // scores when they don't
+    #[derive(Clone)]
+    struct MatchingPennies
+    {
+        rounds: usize,
+        scores: [usize; 2],
+        last_moves: Option<(Coin, Coin)>,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Coin
+    {
+        Heads,
+        Tails,
+    }
+
+    impl Display for Coin
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+        {
+            match self
+            {
+                Coin::Heads => write!(f, "Heads"),
+                Coin::Tails => write!(f, "Tails"),
+            }
+        }
+    }
+
+    impl Display for MatchingPennies
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+        {
+            writeln!(f, "Scores: {} - {}", self.scores[0], self.scores[1])
+        }
+    }
+
+    impl SimultaneousGame for MatchingPennies
+    {
+        type Move = Coin;
+
+        fn get_valid_moves(&self, _player: Player) -> Vec<Self::Move>
+        {
+            vec![Coin::Heads, Coin::Tails]
+        }
+
+        fn do_moves(mut self, p1_move: Self::Move, p2_move: Self::Move) -> Self
+        {
+            match p1_move == p2_move
+            {
+                true => self.scores[1] += 1,
+                false => self.scores[0] += 1,
+            }
+            self.rounds -= 1;
+            self.last_moves = Some((p1_move, p2_move));
+            self
+        }
+
+        fn last_moves(&self) -> Option<(Self::Move, Self::Move)>
+        {
+            self.last_moves
+        }
+
+        fn check_win(&self) -> GameResult
+        {
+            if self.rounds > 0
+            {
+                return GameResult::InProgress;
+            }
+
+            match self.scores[0].cmp(&self.scores[1])
+            {
+                std::cmp::Ordering::Greater => GameResult::Win(Player { id: 1 }),
+                std::cmp::Ordering::Less => GameResult::Win(Player { id: 2 }),
+                std::cmp::Ordering::Equal => GameResult::Draw,
+            }
+        }
+    }
+
+    // plays as `player` does, and keeps the moves it chose
+    #[derive(Clone)]
+    struct Recorder<P>
+    {
+        player: P,
+        moves: Vec<String>,
+    }
+
+    impl<P: SimultaneousPlayer + Clone> SimultaneousPlayer for Recorder<P>
+    {
+        fn choose_simultaneous_move<Game>(
+            &mut self,
+            game_state: &Game,
+            player: Player,
+        ) -> Game::Move
+        where
+            Game: SimultaneousGame,
+        {
+            let m = self.player.choose_simultaneous_move(game_state, player);
+            self.moves.push(m.to_string());
+            m
+        }
+    }
+
+    // copies player 1's most recent move that it can find in the game state, and keeps the moves
+    // it found
+    #[derive(Clone)]
+    struct Copycat
+    {
+        seen: Vec<Option<String>>,
+    }
+
+    impl SimultaneousPlayer for Copycat
+    {
+        fn choose_simultaneous_move<Game>(
+            &mut self,
+            game_state: &Game,
+            player: Player,
+        ) -> Game::Move
+        where
+            Game: SimultaneousGame,
+        {
+            let moves = game_state.get_valid_moves(player);
+            let p1_move = game_state
+                .last_moves()
+                .map(|(p1_move, _)| p1_move.to_string());
+            self.seen.push(p1_move.clone());
+            match p1_move
+            {
+                Some(p1_move) => *moves
+                    .iter()
+                    .find(|m| m.to_string() == p1_move)
+                    .unwrap_or(&moves[0]),
+                None => moves[0],
+            }
+        }
+    }
+
+    #[test]
+    fn test_moves_are_hidden()
+    {
+        // if player 2 could see player 1's coin before choosing it would match every time, but
+        // the only move it can find in each round is player 1's move from the round before
+        let mut p1 = Recorder {
+            player: RandomPlayer::from_seed(0),
+            moves: vec![],
+        };
+        let mut p2 = Copycat { seen: vec![] };
+        let game = MatchingPennies {
+            rounds: 100,
+            scores: [0, 0],
+            last_moves: None,
+        };
+        game.play(&mut p1, &mut p2, false);
+
+        assert_eq!(p1.moves.len(), 100);
+        assert_eq!(p2.seen.len(), 100);
+        assert_eq!(p2.seen[0], None);
+        for round in 1..100
+        {
+            assert_eq!(
+                p2.seen[round].as_ref(),
+                Some(&p1.moves[round - 1]),
+                "{round}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_benchmark_no_games()
+    {
+        // there are no scores to summarise, which shouldn't panic
+        SameGame::new(4, 4, 2, 0).benchmark_player(&mut RandomPlayer::from_seed(0), 0);
+    }
+}
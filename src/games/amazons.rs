@@ -0,0 +1,357 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Position},
+        on_board, tuple_to_pos,
+    },
+    GameResult, GameState, Player,
+};
+
+const DIRECTIONS: [(i128, i128); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The Game of the Amazons on a 10x10 board, with four amazons each.
+///
+/// Every move moves one of your amazons like a chess queen, and then the amazon shoots an arrow,
+/// also like a queen, which blocks that square for the rest of the game. The player who can't move
+/// loses.
+#[derive(Clone)]
+pub struct Amazons
+{
+    board: Board<AmazonsCell>,
+    last_move: Option<AmazonsMove>,
+}
+
+impl Amazons
+{
+    pub fn new() -> Amazons
+    {
+        let mut board = Board::new(10, 10);
+        for (row, col) in [(6, 0), (9, 3), (9, 6), (6, 9)]
+        {
+            board[Position { row, col }] = AmazonsCell::Amazon(Player { id: 1 });
+        }
+        for (row, col) in [(3, 0), (0, 3), (0, 6), (3, 9)]
+        {
+            board[Position { row, col }] = AmazonsCell::Amazon(Player { id: 2 });
+        }
+
+        Amazons {
+            board,
+            last_move: None,
+        }
+    }
+
+    // every empty square a queen at `from` could reach
+    fn reachable(board: &Board<AmazonsCell>, from: Position) -> Vec<Position>
+    {
+        let size = (board.rows(), board.cols());
+
+        let mut positions = vec![];
+        for dir in DIRECTIONS
+        {
+            let mut pos = (from.row as i128 + dir.0, from.col as i128 + dir.1);
+            while on_board(pos, size) && board[tuple_to_pos(pos)] == AmazonsCell::Empty
+            {
+                positions.push(tuple_to_pos(pos));
+                pos.0 += dir.0;
+                pos.1 += dir.1;
+            }
+        }
+
+        positions
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<AmazonsMove>
+    {
+        let mut moves = vec![];
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                let from = Position { row, col };
+                if self.board[from] != AmazonsCell::Amazon(player)
+                {
+                    continue;
+                }
+
+                // the amazon has left its square by the time it shoots, so it can shoot through
+                // or into the square it came from
+                let mut board = self.board.clone();
+                board[from] = AmazonsCell::Empty;
+                for to in Amazons::reachable(&board, from)
+                {
+                    for arrow in Amazons::reachable(&board, to)
+                    {
+                        moves.push(AmazonsMove {
+                            from,
+                            to,
+                            arrow,
+                            player,
+                        });
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+impl Default for Amazons
+{
+    fn default() -> Self
+    {
+        Amazons::new()
+    }
+}
+
+impl GameState for Amazons
+{
+    type Move = AmazonsMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        self.moves_for(self.player_to_move())
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if self.board[m.from] != AmazonsCell::Amazon(m.player)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        let mut board = self.board.clone();
+        board[m.from] = AmazonsCell::Empty;
+        if !Amazons::reachable(&board, m.from).contains(&m.to)
+            || !Amazons::reachable(&board, m.to).contains(&m.arrow)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board = board;
+        self.board[m.to] = AmazonsCell::Amazon(m.player);
+        self.board[m.arrow] = AmazonsCell::Arrow;
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let player_to_move = self.player_to_move();
+
+        // an amazon that can move can always shoot back into the square it came from, so the
+        // player can move as long as any of their amazons has an empty neighbour
+        let size = (self.board.rows(), self.board.cols());
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                let from = Position { row, col };
+                if self.board[from] != AmazonsCell::Amazon(player_to_move)
+                {
+                    continue;
+                }
+
+                let can_move = DIRECTIONS.iter().any(|dir| {
+                    let pos = (row as i128 + dir.0, col as i128 + dir.1);
+                    on_board(pos, size) && self.board[tuple_to_pos(pos)] == AmazonsCell::Empty
+                });
+                if can_move
+                {
+                    return GameResult::InProgress;
+                }
+            }
+        }
+
+        GameResult::Win(Player {
+            id: if player_to_move.id == 1 { 2 } else { 1 },
+        })
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Amazons
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum AmazonsCell
+{
+    #[default]
+    Empty,
+    Amazon(Player),
+    Arrow,
+}
+
+impl Display for AmazonsCell
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            AmazonsCell::Empty => write!(f, "-"),
+            AmazonsCell::Amazon(player) => write!(f, "{}", player.id),
+            AmazonsCell::Arrow => write!(f, "#"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct AmazonsMove
+{
+    from: Position,
+    to: Position,
+    arrow: Position,
+    player: Player,
+}
+
+impl Display for AmazonsMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}, From: {}, To: {}, Arrow: {}",
+            self.player, self.from, self.to, self.arrow
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{
+        common::board::{Board, Position},
+        GameResult, GameState, Player,
+    };
+
+    use crate::players::{mcts::MCTSPlayer, GamePlayer};
+
+    use super::{Amazons, AmazonsCell, AmazonsMove};
+
+    #[test]
+    fn test_opening_moves()
+    {
+        let game = Amazons::new();
+        assert_eq!(game.get_valid_moves().len(), 2176);
+
+        let m = game.get_valid_moves()[0];
+        let game = game.do_move(m);
+        assert!(game.board[m.from] == AmazonsCell::Empty || m.arrow == m.from);
+        assert!(game.board[m.arrow] == AmazonsCell::Arrow);
+        assert!(game.player_to_move() == Player::new(2));
+    }
+
+    #[test]
+    fn test_no_moves_loses()
+    {
+        // player 2's amazon is walled into the corner, except for one square
+        let mut game = Amazons {
+            board: Board::new(10, 10),
+            last_move: None,
+        };
+        game.board[Position { row: 5, col: 5 }] = AmazonsCell::Amazon(Player::new(1));
+        game.board[Position { row: 0, col: 0 }] = AmazonsCell::Amazon(Player::new(2));
+        game.board[Position { row: 0, col: 1 }] = AmazonsCell::Arrow;
+        game.board[Position { row: 1, col: 0 }] = AmazonsCell::Arrow;
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // shooting into the last open square leaves player 2 without a move
+        let game = game.do_move(AmazonsMove {
+            from: Position { row: 5, col: 5 },
+            to: Position { row: 5, col: 1 },
+            arrow: Position { row: 1, col: 1 },
+            player: Player::new(1),
+        });
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    fn test_shoot_at_start()
+    {
+        let mut game = Amazons {
+            board: Board::new(10, 10),
+            last_move: None,
+        };
+        game.board[Position { row: 0, col: 0 }] = AmazonsCell::Amazon(Player::new(1));
+        game.board[Position { row: 9, col: 9 }] = AmazonsCell::Amazon(Player::new(2));
+
+        // the square the amazon moved from is empty, so the arrow can land on it
+        let game = game.do_move(AmazonsMove {
+            from: Position { row: 0, col: 0 },
+            to: Position { row: 0, col: 2 },
+            arrow: Position { row: 0, col: 0 },
+            player: Player::new(1),
+        });
+        assert!(game.board[Position { row: 0, col: 0 }] == AmazonsCell::Arrow);
+        assert!(game.board[Position { row: 0, col: 2 }] == AmazonsCell::Amazon(Player::new(1)));
+    }
+
+    #[test]
+    fn test_mcts_opening()
+    {
+        // a seeded search picks the same opening move every time, and it's one of the real ones
+        let game = Amazons::new();
+        let m = MCTSPlayer::from_seed(200, 0).choose_move(&game);
+        let again = MCTSPlayer::from_seed(200, 0).choose_move(&game);
+        assert_eq!(m.to_string(), again.to_string());
+        assert!(game
+            .get_valid_moves()
+            .iter()
+            .any(|valid| valid.to_string() == m.to_string()));
+
+        let game = game.do_move(m);
+        assert!(game.player_to_move() == Player::new(2));
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+}
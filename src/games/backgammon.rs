@@ -0,0 +1,642 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{GameResult, GameState, Player};
+
+const CHECKERS: u8 = 15;
+
+// point numbers are relative to the player moving, counting down from 24 towards their home
+// board on points 1 to 6
+const BAR: u8 = 25;
+const OFF: u8 = 0;
+
+/// Backgammon, with the dice modelled as chance events.
+///
+/// Each turn starts with a dice roll, which is chosen by `GameState::chance_outcomes` rather than by
+/// a player, followed by a single move that plays as much of the roll as possible. Player 1
+/// rolls first.
+#[derive(Clone)]
+pub struct Backgammon
+{
+    // checkers on each point, numbered from player 1's side. Positive counts are player 1's
+    // checkers and negative counts are player 2's.
+    points: [i8; 24],
+    bar: [u8; 2],
+    off: [u8; 2],
+    to_move: Player,
+    dice: Option<(u8, u8)>,
+    last_move: Option<BackgammonMove>,
+}
+
+impl Backgammon
+{
+    pub fn new() -> Backgammon
+    {
+        let mut game = Backgammon {
+            points: [0; 24],
+            bar: [0; 2],
+            off: [0; 2],
+            to_move: Player { id: 1 },
+            dice: None,
+            last_move: None,
+        };
+
+        for player in [Player { id: 1 }, Player { id: 2 }]
+        {
+            for (point, count) in [(24, 2), (13, 5), (8, 3), (6, 5)]
+            {
+                game.set(player, point, count);
+            }
+        }
+
+        game
+    }
+
+    /// The number of points won: 1 for a normal win, 2 for a gammon (the loser hasn't borne off
+    /// any checkers) and 3 for a backgammon (the loser also has a checker on the bar or in the
+    /// winner's home board). Returns 0 while the game is in progress.
+    pub fn points_won(&self) -> usize
+    {
+        let winner = match self.check_win()
+        {
+            GameResult::Win(winner) => winner,
+            _ => return 0,
+        };
+        let loser = opponent(winner);
+
+        if self.off[index(loser)] > 0
+        {
+            1
+        }
+        else if self.bar[index(loser)] > 0 || (19..=24).any(|point| self.count(loser, point) > 0)
+        {
+            3
+        }
+        else
+        {
+            2
+        }
+    }
+
+    fn point_index(player: Player, point: u8) -> usize
+    {
+        match player.id
+        {
+            1 => point as usize - 1,
+            _ => 24 - point as usize,
+        }
+    }
+
+    /// Number of `player`'s checkers on `point`
+    fn count(&self, player: Player, point: u8) -> u8
+    {
+        let count = self.points[Backgammon::point_index(player, point)];
+        match player.id
+        {
+            1 => count.max(0) as u8,
+            _ => (-count).max(0) as u8,
+        }
+    }
+
+    fn set(&mut self, player: Player, point: u8, count: u8)
+    {
+        self.points[Backgammon::point_index(player, point)] = match player.id
+        {
+            1 => count as i8,
+            _ => -(count as i8),
+        };
+    }
+
+    /// Whether `player` can land on `point`, which they can't if the opponent has two or more
+    /// checkers there
+    fn is_open(&self, player: Player, point: u8) -> bool
+    {
+        // the opponent numbers the points from the other end of the board
+        self.count(opponent(player), 25 - point) < 2
+    }
+
+    fn can_bear_off(&self, player: Player) -> bool
+    {
+        let home: u8 = (1..=6).map(|point| self.count(player, point)).sum();
+        home + self.off[index(player)] == CHECKERS
+    }
+
+    fn step(&self, player: Player, from: u8, die: u8) -> Option<Step>
+    {
+        if self.bar[index(player)] > 0
+        {
+            return match from == BAR && self.is_open(player, BAR - die)
+            {
+                true => Some(Step {
+                    from,
+                    to: BAR - die,
+                    die,
+                }),
+                false => None,
+            };
+        }
+
+        if from == BAR || self.count(player, from) == 0
+        {
+            return None;
+        }
+
+        if from > die
+        {
+            return match self.is_open(player, from - die)
+            {
+                true => Some(Step {
+                    from,
+                    to: from - die,
+                    die,
+                }),
+                false => None,
+            };
+        }
+
+        // a checker can bear off with a higher roll than it needs only from the highest occupied point
+        let exact = from == die;
+        let highest = (from + 1..=6).all(|point| self.count(player, point) == 0);
+        match self.can_bear_off(player) && (exact || highest)
+        {
+            true => Some(Step { from, to: OFF, die }),
+            false => None,
+        }
+    }
+
+    fn apply_step(&mut self, player: Player, step: Step)
+    {
+        match step.from
+        {
+            BAR => self.bar[index(player)] -= 1,
+            from => self.set(player, from, self.count(player, from) - 1),
+        }
+
+        if step.to == OFF
+        {
+            self.off[index(player)] += 1;
+            return;
+        }
+
+        // hitting a single opposing checker sends it to the bar
+        let opponent = opponent(player);
+        if self.count(opponent, 25 - step.to) == 1
+        {
+            self.set(opponent, 25 - step.to, 0);
+            self.bar[index(opponent)] += 1;
+        }
+
+        self.set(player, step.to, self.count(player, step.to) + 1);
+    }
+
+    /// Finds every way of playing `dice` in order, stopping each sequence once no more dice can be used
+    fn sequences(
+        &self,
+        player: Player,
+        dice: &[u8],
+        doubles: bool,
+        steps: &mut Vec<Step>,
+        out: &mut Vec<Vec<Step>>,
+    )
+    {
+        let mut can_continue = false;
+
+        for (i, &die) in dice.iter().enumerate()
+        {
+            // with doubles every die is the same, so only the first needs to be tried
+            if dice[..i].contains(&die)
+            {
+                continue;
+            }
+
+            for from in (1..=BAR).rev()
+            {
+                // with doubles the order the checkers are moved in doesn't matter, so only look at
+                // sequences that move checkers from the back first to avoid repeating the same move
+                if doubles && steps.last().is_some_and(|last| from > last.from)
+                {
+                    continue;
+                }
+
+                if let Some(step) = self.step(player, from, die)
+                {
+                    can_continue = true;
+
+                    let mut next = self.clone();
+                    next.apply_step(player, step);
+
+                    let mut remaining = dice.to_vec();
+                    remaining.remove(i);
+
+                    steps.push(step);
+                    next.sequences(player, &remaining, doubles, steps, out);
+                    steps.pop();
+                }
+            }
+        }
+
+        if !can_continue
+        {
+            out.push(steps.clone());
+        }
+    }
+
+    fn position_after(&self, player: Player, steps: &[Step]) -> ([i8; 24], [u8; 2], [u8; 2])
+    {
+        let mut game = self.clone();
+        for &step in steps
+        {
+            game.apply_step(player, step);
+        }
+        (game.points, game.bar, game.off)
+    }
+}
+
+impl Default for Backgammon
+{
+    fn default() -> Self
+    {
+        Backgammon::new()
+    }
+}
+
+impl GameState for Backgammon
+{
+    type Move = BackgammonMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let (a, b) = match self.dice
+        {
+            Some(dice) => dice,
+            None =>
+            {
+                return self
+                    .chance_outcomes()
+                    .unwrap()
+                    .into_iter()
+                    .map(|(m, _)| m)
+                    .collect()
+            },
+        };
+
+        let dice = match a == b
+        {
+            true => vec![a; 4],
+            false => vec![a, b],
+        };
+
+        let mut sequences = vec![];
+        self.sequences(self.to_move, &dice, a == b, &mut vec![], &mut sequences);
+
+        // as many dice as possible have to be used, and if only one of two different dice can be
+        // used, it has to be the larger one if possible
+        let most_steps = sequences.iter().map(|s| s.len()).max().unwrap_or(0);
+        sequences.retain(|s| s.len() == most_steps);
+        if most_steps == 1 && a != b
+        {
+            let larger = a.max(b);
+            let uses_larger = |s: &Vec<Step>| s[0].die == larger;
+            if sequences.iter().any(uses_larger)
+            {
+                sequences.retain(uses_larger);
+            }
+        }
+
+        // different orders of the same steps usually lead to the same position, which only counts once
+        let mut positions = vec![];
+        let mut moves = vec![];
+        for sequence in sequences
+        {
+            let position = self.position_after(self.to_move, &sequence);
+            if positions.contains(&position)
+            {
+                continue;
+            }
+            positions.push(position);
+
+            let mut steps = [None; 4];
+            for (i, step) in sequence.into_iter().enumerate()
+            {
+                steps[i] = Some(step);
+            }
+            moves.push(BackgammonMove::Play {
+                player: self.to_move,
+                steps,
+            });
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        match m
+        {
+            BackgammonMove::Roll(a, b) => self.dice = Some((a, b)),
+            BackgammonMove::Play { player, steps } =>
+            {
+                for step in steps.into_iter().flatten()
+                {
+                    self.apply_step(player, step);
+                }
+                self.dice = None;
+                self.to_move = opponent(player);
+            },
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        for player in [Player { id: 1 }, Player { id: 2 }]
+        {
+            if self.off[index(player)] == CHECKERS
+            {
+                return GameResult::Win(player);
+            }
+        }
+
+        GameResult::InProgress
+    }
+
+    fn chance_outcomes(&self) -> Option<Vec<(Self::Move, f64)>>
+    {
+        if self.dice.is_some() || self.check_win() != GameResult::InProgress
+        {
+            return None;
+        }
+
+        let mut outcomes = vec![];
+        for a in 1..=6
+        {
+            for b in a..=6
+            {
+                let p = match a == b
+                {
+                    true => 1.0 / 36.0,
+                    false => 2.0 / 36.0,
+                };
+                outcomes.push((BackgammonMove::Roll(a, b), p));
+            }
+        }
+
+        Some(outcomes)
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Backgammon
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        // points are numbered from player 1's side, with player 1's checkers shown as positive
+        // counts and player 2's as negative counts
+        writeln!(f, "Board: ")?;
+        for point in 13..=24
+        {
+            write!(f, "{:>4}", self.points[point - 1])?;
+        }
+        writeln!(f)?;
+        for point in (1..=12).rev()
+        {
+            write!(f, "{:>4}", self.points[point - 1])?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Bar: {} / {}", self.bar[0], self.bar[1])?;
+        writeln!(f, "Off: {} / {}", self.off[0], self.off[1])?;
+
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+            if let Some((a, b)) = self.dice
+            {
+                writeln!(f, "Dice: {a} {b}")?;
+            }
+        }
+        else
+        {
+            writeln!(
+                f,
+                "Result: {} ({} points)",
+                self.check_win(),
+                self.points_won()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BackgammonMove
+{
+    Roll(u8, u8),
+    /// Up to four steps, one for each die used
+    Play
+    {
+        player: Player,
+        steps: [Option<Step>; 4],
+    },
+}
+
+impl Display for BackgammonMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            BackgammonMove::Roll(a, b) => write!(f, "Roll: {a} {b}"),
+            BackgammonMove::Play { player, steps } =>
+            {
+                write!(f, "{player}:")?;
+                if steps[0].is_none()
+                {
+                    write!(f, " No Move")?;
+                }
+                for step in steps.iter().flatten()
+                {
+                    write!(f, " {step}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// A single checker moved by one die, written in the usual `from/to` notation with point numbers
+/// relative to the player moving
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Step
+{
+    from: u8,
+    to: u8,
+    die: u8,
+}
+
+impl Display for Step
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self.from
+        {
+            BAR => write!(f, "bar/")?,
+            from => write!(f, "{from}/")?,
+        }
+        match self.to
+        {
+            OFF => write!(f, "off"),
+            to => write!(f, "{to}"),
+        }
+    }
+}
+
+fn index(player: Player) -> usize
+{
+    player.id - 1
+}
+
+fn opponent(player: Player) -> Player
+{
+    match player.id
+    {
+        1 => Player { id: 2 },
+        _ => Player { id: 1 },
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{GameResult, GameState, Player};
+
+    use super::{Backgammon, BackgammonMove, BAR};
+
+    // a board with no checkers left on it, which the tests then add checkers to
+    fn empty() -> Backgammon
+    {
+        let mut game = Backgammon::new();
+        game.points = [0; 24];
+        game.off = [15, 15];
+        game
+    }
+
+    fn place(game: &mut Backgammon, player: Player, point: u8, count: u8)
+    {
+        game.set(player, point, count);
+        game.off[player.id - 1] -= count;
+    }
+
+    fn plays(game: &Backgammon, a: u8, b: u8) -> Vec<Vec<(u8, u8)>>
+    {
+        game.clone()
+            .do_move(BackgammonMove::Roll(a, b))
+            .get_valid_moves()
+            .into_iter()
+            .map(|m| match m
+            {
+                BackgammonMove::Play { steps, .. } => steps
+                    .iter()
+                    .flatten()
+                    .map(|step| (step.from, step.to))
+                    .collect(),
+                BackgammonMove::Roll(..) => panic!("expected a play"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dice()
+    {
+        let game = Backgammon::new();
+        let outcomes = game.chance_outcomes().unwrap();
+        assert_eq!(outcomes.len(), 21);
+        assert!((outcomes.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // every opening roll can use both dice, and doubles use all four
+        assert!(plays(&game, 6, 5).iter().all(|p| p.len() == 2));
+        assert!(plays(&game, 3, 3).iter().all(|p| p.len() == 4));
+        assert!(game
+            .clone()
+            .do_move(BackgammonMove::Roll(6, 5))
+            .chance_outcomes()
+            .is_none());
+    }
+
+    #[test]
+    fn test_larger_die()
+    {
+        // the checker on 10 can move 1 or 6, but then it is stuck behind the block on 3
+        let p1 = Player::new(1);
+        let p2 = Player::new(2);
+        let mut game = empty();
+        place(&mut game, p1, 10, 1);
+        place(&mut game, p2, 22, 2);
+
+        assert_eq!(plays(&game, 6, 1), vec![vec![(10, 4)]]);
+    }
+
+    #[test]
+    fn test_bar()
+    {
+        // player 2's checker is hit, and then has to enter from the bar before anything else
+        let p1 = Player::new(1);
+        let p2 = Player::new(2);
+        let mut game = empty();
+        place(&mut game, p1, 8, 2);
+        place(&mut game, p2, 20, 1);
+        place(&mut game, p2, 6, 2);
+
+        let game = game.do_move(BackgammonMove::Roll(3, 1));
+        let hit = game
+            .get_valid_moves()
+            .into_iter()
+            .find(|m| m.to_string() == "Player 1: 8/5 8/7")
+            .unwrap();
+        let game = game.do_move(hit);
+        assert_eq!(game.bar, [0, 1]);
+
+        for play in plays(&game, 4, 2)
+        {
+            assert_eq!(play[0].0, BAR);
+        }
+    }
+
+    #[test]
+    fn test_points_won()
+    {
+        let p1 = Player::new(1);
+        let p2 = Player::new(2);
+        let mut game = empty();
+        place(&mut game, p1, 1, 1);
+        place(&mut game, p2, 1, 15);
+
+        let finished = game.clone().do_move(BackgammonMove::Roll(2, 1));
+        let play = finished.get_valid_moves()[0];
+        let finished = finished.do_move(play);
+        assert!(finished.check_win() == GameResult::Win(p1));
+        assert_eq!(finished.points_won(), 2);
+
+        // with a checker still in player 1's home board, it's a backgammon
+        game.set(p2, 1, 14);
+        game.set(p2, 19, 1);
+        let finished = game.do_move(BackgammonMove::Roll(2, 1));
+        let play = finished.get_valid_moves()[0];
+        assert_eq!(play.to_string(), "Player 1: 1/off");
+        assert_eq!(finished.do_move(play).points_won(), 3);
+    }
+}
@@ -0,0 +1,610 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{BitAnd, BitOr, Not},
+    sync::OnceLock,
+};
+
+use crate::games::{
+    common::board::{Board, Cell, Position},
+    GameResult, GameState, Player,
+};
+
+const SIZE: usize = 14;
+
+/// The squares each player's first piece has to cover
+const STARTS: [Position; 2] = [Position { row: 4, col: 4 }, Position { row: 9, col: 9 }];
+
+/// The 21 pieces each player has, as the squares they cover
+const PIECES: [&[(usize, usize)]; 21] = [
+    // 1 square
+    &[(0, 0)],
+    // 2 squares
+    &[(0, 0), (0, 1)],
+    // 3 squares
+    &[(0, 0), (0, 1), (0, 2)],
+    &[(0, 0), (1, 0), (1, 1)],
+    // 4 squares
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1)],
+    &[(0, 0), (0, 1), (0, 2), (1, 1)],
+    &[(0, 0), (0, 1), (1, 0), (1, 1)],
+    &[(0, 1), (0, 2), (1, 0), (1, 1)],
+    // 5 squares
+    &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)],
+    &[(0, 0), (1, 0), (2, 0), (3, 0), (3, 1)],
+    &[(0, 1), (1, 1), (2, 0), (2, 1), (3, 0)],
+    &[(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)],
+    &[(0, 0), (0, 1), (0, 2), (1, 1), (2, 1)],
+    &[(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)],
+    &[(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
+    &[(0, 1), (1, 0), (1, 1), (2, 1), (3, 1)],
+    &[(0, 0), (0, 1), (1, 1), (2, 1), (2, 2)],
+];
+
+/// Blokus Duo, where two players take turns placing polyomino pieces on a 14x14 board.
+///
+/// Each player's first piece has to cover their starting square. After that, every piece has to
+/// touch one of the player's own pieces at a corner, and can't share an edge with any of them.
+/// Pieces can be rotated and reflected. A player who can't place a piece passes, and the game ends
+/// when both players pass in a row. Players lose a point for each square in their unplaced
+/// pieces, or get 15 points for placing every piece, and 20 if the last one was the single square.
+///
+/// Every way of placing a piece on the board is worked out once and shared between games, so a
+/// move only has to store an index into that list.
+#[derive(Clone)]
+pub struct BlokusDuo
+{
+    board: Board<Cell>,
+    // the squares covered by each player, for checking placements quickly
+    covered: [BitBoard; 2],
+    // a bit for each piece that hasn't been placed yet
+    unplaced: [u32; 2],
+    last_piece: [Option<usize>; 2],
+    passes: usize,
+    last_move: Option<BlokusDuoMove>,
+}
+
+impl BlokusDuo
+{
+    pub fn new() -> BlokusDuo
+    {
+        BlokusDuo {
+            board: Board::new(SIZE, SIZE),
+            covered: [BitBoard::default(); 2],
+            unplaced: [(1 << PIECES.len()) - 1; 2],
+            last_piece: [None, None],
+            passes: 0,
+            last_move: None,
+        }
+    }
+
+    /// The score `player` would get if the game ended now
+    pub fn score(&self, player: Player) -> i32
+    {
+        let i = player.id - 1;
+        if self.unplaced[i] == 0
+        {
+            return match self.last_piece[i]
+            {
+                Some(0) => 20,
+                _ => 15,
+            };
+        }
+
+        -((0..PIECES.len())
+            .filter(|&piece| self.unplaced[i] & (1 << piece) != 0)
+            .map(|piece| PIECES[piece].len() as i32)
+            .sum::<i32>())
+    }
+
+    // whether `player` can put down `placement` on the board as it is now
+    fn can_place(&self, player: Player, placement: &Placement) -> bool
+    {
+        let i = player.id - 1;
+        let occupied = self.covered[0] | self.covered[1];
+        let own = self.covered[i];
+        let first = self.unplaced[i] == (1 << PIECES.len()) - 1;
+
+        self.unplaced[i] & (1 << placement.piece) != 0
+            && (placement.squares & occupied).is_empty()
+            && (placement.edges & own).is_empty()
+            && match first
+            {
+                true => !(placement.squares & BitBoard::square(STARTS[i])).is_empty(),
+                false => !(placement.corners & own).is_empty(),
+            }
+    }
+
+    fn placements_for(&self, player: Player) -> Vec<BlokusDuoMove>
+    {
+        placements()
+            .iter()
+            .enumerate()
+            .filter(|(_, placement)| self.can_place(player, placement))
+            .map(|(index, _)| BlokusDuoMove {
+                placement: Some(index as u16),
+                player,
+            })
+            .collect()
+    }
+}
+
+impl Default for BlokusDuo
+{
+    fn default() -> Self
+    {
+        BlokusDuo::new()
+    }
+}
+
+impl GameState for BlokusDuo
+{
+    type Move = BlokusDuoMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let player = self.player_to_move();
+        let moves = self.placements_for(player);
+        match moves.is_empty()
+        {
+            true => vec![BlokusDuoMove {
+                placement: None,
+                player,
+            }],
+            false => moves,
+        }
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) if last_move.player.id == 1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        // passing is only allowed when there is nowhere to place a piece
+        let valid = m.player == self.player_to_move()
+            && match m.placement
+            {
+                Some(index) => placements()
+                    .get(index as usize)
+                    .is_some_and(|placement| self.can_place(m.player, placement)),
+                None => !placements()
+                    .iter()
+                    .any(|placement| self.can_place(m.player, placement)),
+            };
+        if !valid
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        let i = m.player.id - 1;
+        match m.placement
+        {
+            Some(index) =>
+            {
+                let placement = &placements()[index as usize];
+                for &position in &placement.positions
+                {
+                    self.board[position] = Cell::Piece(m.player);
+                }
+                self.covered[i] = self.covered[i] | placement.squares;
+                self.unplaced[i] &= !(1 << placement.piece);
+                self.last_piece[i] = Some(placement.piece);
+                self.passes = 0;
+            },
+            None => self.passes += 1,
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if self.passes < 2
+        {
+            return GameResult::InProgress;
+        }
+
+        match self
+            .score(Player { id: 1 })
+            .cmp(&self.score(Player { id: 2 }))
+        {
+            std::cmp::Ordering::Greater => GameResult::Win(Player { id: 1 }),
+            std::cmp::Ordering::Less => GameResult::Win(Player { id: 2 }),
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for BlokusDuo
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        writeln!(
+            f,
+            "Scores: {} - {}",
+            self.score(Player { id: 1 }),
+            self.score(Player { id: 2 })
+        )?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+/// Either a placement, as an index into the list of every placement on the board, or a pass
+#[derive(Clone, Copy)]
+pub struct BlokusDuoMove
+{
+    placement: Option<u16>,
+    player: Player,
+}
+
+impl Display for BlokusDuoMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self.placement
+        {
+            Some(index) =>
+            {
+                let placement = &placements()[index as usize];
+                write!(f, "{}, Piece: {}, Squares:", self.player, placement.piece)?;
+                for position in &placement.positions
+                {
+                    write!(f, " {position}")?;
+                }
+                Ok(())
+            },
+            None => write!(f, "{}, Pass", self.player),
+        }
+    }
+}
+
+// one of the 196 squares of the board per bit
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct BitBoard([u64; 4]);
+
+impl BitBoard
+{
+    fn square(position: Position) -> BitBoard
+    {
+        let index = position.row * SIZE + position.col;
+        let mut bits = [0; 4];
+        bits[index / 64] = 1 << (index % 64);
+        BitBoard(bits)
+    }
+
+    fn is_empty(&self) -> bool
+    {
+        self.0.iter().all(|&bits| bits == 0)
+    }
+}
+
+impl BitAnd for BitBoard
+{
+    type Output = BitBoard;
+
+    fn bitand(self, other: BitBoard) -> BitBoard
+    {
+        BitBoard(std::array::from_fn(|i| self.0[i] & other.0[i]))
+    }
+}
+
+impl BitOr for BitBoard
+{
+    type Output = BitBoard;
+
+    fn bitor(self, other: BitBoard) -> BitBoard
+    {
+        BitBoard(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+}
+
+impl Not for BitBoard
+{
+    type Output = BitBoard;
+
+    fn not(self) -> BitBoard
+    {
+        BitBoard(self.0.map(|bits| !bits))
+    }
+}
+
+// one way of placing a piece on the board
+struct Placement
+{
+    piece: usize,
+    positions: Vec<Position>,
+    squares: BitBoard,
+    // empty squares sharing an edge with the piece, which can't be the player's own
+    edges: BitBoard,
+    // empty squares only touching the piece at a corner, one of which has to be the player's own
+    corners: BitBoard,
+}
+
+/// Every distinct rotation and reflection of `piece`, with each moved as close to (0, 0) as it
+/// can go
+fn orientations(piece: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>>
+{
+    let mut orientations: Vec<Vec<(usize, usize)>> = vec![];
+    let mut squares: Vec<(i128, i128)> = piece
+        .iter()
+        .map(|&(row, col)| (row as i128, col as i128))
+        .collect();
+
+    for reflection in 0..2
+    {
+        for _ in 0..4
+        {
+            // rotate a quarter turn
+            squares = squares.iter().map(|&(row, col)| (col, -row)).collect();
+
+            let min_row = squares.iter().map(|square| square.0).min().unwrap();
+            let min_col = squares.iter().map(|square| square.1).min().unwrap();
+            let mut normalised: Vec<(usize, usize)> = squares
+                .iter()
+                .map(|&(row, col)| ((row - min_row) as usize, (col - min_col) as usize))
+                .collect();
+            normalised.sort_unstable();
+
+            if !orientations.contains(&normalised)
+            {
+                orientations.push(normalised);
+            }
+        }
+
+        if reflection == 0
+        {
+            squares = squares.iter().map(|&(row, col)| (row, -col)).collect();
+        }
+    }
+
+    orientations
+}
+
+fn placements() -> &'static Vec<Placement>
+{
+    static PLACEMENTS: OnceLock<Vec<Placement>> = OnceLock::new();
+    PLACEMENTS.get_or_init(|| {
+        let on_board =
+            |row: i128, col: i128| row >= 0 && col >= 0 && row < SIZE as i128 && col < SIZE as i128;
+        let square = |row: i128, col: i128| {
+            BitBoard::square(Position {
+                row: row as usize,
+                col: col as usize,
+            })
+        };
+
+        let mut placements = vec![];
+        for (piece, shape) in PIECES.iter().enumerate()
+        {
+            for orientation in orientations(shape)
+            {
+                let height = orientation.iter().map(|square| square.0).max().unwrap() + 1;
+                let width = orientation.iter().map(|square| square.1).max().unwrap() + 1;
+
+                for row in 0..=SIZE - height
+                {
+                    for col in 0..=SIZE - width
+                    {
+                        let positions: Vec<Position> = orientation
+                            .iter()
+                            .map(|&(r, c)| Position {
+                                row: row + r,
+                                col: col + c,
+                            })
+                            .collect();
+                        let mut squares = BitBoard::default();
+                        for &position in &positions
+                        {
+                            squares = squares | BitBoard::square(position);
+                        }
+
+                        let mut edges = BitBoard::default();
+                        let mut corners = BitBoard::default();
+                        for position in &positions
+                        {
+                            let (r, c) = (position.row as i128, position.col as i128);
+                            for (d_row, d_col) in [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                            {
+                                if on_board(r + d_row, c + d_col)
+                                {
+                                    edges = edges | square(r + d_row, c + d_col);
+                                }
+                            }
+                            for (d_row, d_col) in [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+                            {
+                                if on_board(r + d_row, c + d_col)
+                                {
+                                    corners = corners | square(r + d_row, c + d_col);
+                                }
+                            }
+                        }
+
+                        // leave out squares that are part of the piece, or next to it
+                        edges = edges & !squares;
+                        corners = corners & !(squares | edges);
+
+                        placements.push(Placement {
+                            piece,
+                            positions,
+                            squares,
+                            edges,
+                            corners,
+                        });
+                    }
+                }
+            }
+        }
+
+        placements
+    })
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{common::board::Position, GameResult, GameState, Player},
+        players::{random::RandomPlayer, GamePlayer},
+    };
+
+    use super::{orientations, placements, BlokusDuo, BlokusDuoMove, PIECES};
+
+    fn place(game: BlokusDuo, piece: usize, positions: &[(usize, usize)]) -> BlokusDuo
+    {
+        let positions: Vec<Position> = positions
+            .iter()
+            .map(|&(row, col)| Position { row, col })
+            .collect();
+        let index = placements()
+            .iter()
+            .position(|placement| placement.piece == piece && placement.positions == positions)
+            .unwrap();
+
+        let player = game.player_to_move();
+        game.do_move(BlokusDuoMove {
+            placement: Some(index as u16),
+            player,
+        })
+    }
+
+    fn placed_squares(game: &BlokusDuo) -> Vec<Vec<Position>>
+    {
+        game.get_valid_moves()
+            .iter()
+            .filter_map(|m| m.placement)
+            .map(|index| placements()[index as usize].positions.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_pieces()
+    {
+        assert_eq!(PIECES.iter().map(|piece| piece.len()).sum::<usize>(), 89);
+        assert_eq!(
+            PIECES
+                .iter()
+                .map(|piece| orientations(piece).len())
+                .sum::<usize>(),
+            91
+        );
+    }
+
+    #[test]
+    fn test_placement_rules()
+    {
+        // the first piece has to cover the starting square
+        let game = BlokusDuo::new();
+        let start = Position { row: 4, col: 4 };
+        assert!(placed_squares(&game)
+            .iter()
+            .all(|positions| positions.contains(&start)));
+
+        let game = place(game, 0, &[(4, 4)]);
+        let game = place(game, 0, &[(9, 9)]);
+
+        // the only places for player 1's domino are touching the corners of their first piece
+        let moves: Vec<Vec<Position>> = placed_squares(&game)
+            .into_iter()
+            .filter(|positions| positions.len() == 2)
+            .collect();
+        assert_eq!(moves.len(), 4 * 2);
+        assert!(moves.iter().all(|positions| {
+            positions.iter().all(|position| {
+                position.row.abs_diff(4) + position.col.abs_diff(4) > 1
+                    && position.row.abs_diff(4) <= 2
+                    && position.col.abs_diff(4) <= 2
+            })
+        }));
+
+        // the single square has been used up
+        assert!(placed_squares(&game)
+            .iter()
+            .all(|positions| positions.len() > 1));
+    }
+
+    #[test]
+    fn test_passing_and_scoring()
+    {
+        let mut game = BlokusDuo::new();
+        game.unplaced = [0, 0b10];
+        game.last_piece = [Some(0), Some(5)];
+        assert_eq!(game.score(Player::new(1)), 20);
+        assert_eq!(game.score(Player::new(2)), -2);
+
+        // player 1 has nothing left to place, so they have to pass
+        let moves = game.get_valid_moves();
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].placement.is_none());
+
+        let game = game.do_move(moves[0]);
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pass_with_moves_left()
+    {
+        BlokusDuo::new().do_move(BlokusDuoMove {
+            placement: None,
+            player: Player::new(1),
+        });
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let mut game = BlokusDuo::new();
+        let mut players = [RandomPlayer::from_seed(0), RandomPlayer::from_seed(1)];
+        while game.check_win() == GameResult::InProgress
+        {
+            let player = &mut players[game.player_to_move().id - 1];
+            let m = player.choose_move(&game);
+            game = game.do_move(m);
+        }
+
+        // the squares on the board add up to the pieces that were placed
+        for id in [1, 2]
+        {
+            let unplaced: usize = (0..PIECES.len())
+                .filter(|&piece| game.unplaced[id - 1] & (1 << piece) != 0)
+                .map(|piece| PIECES[piece].len())
+                .sum();
+            let covered: u32 = game.covered[id - 1]
+                .0
+                .iter()
+                .map(|bits| bits.count_ones())
+                .sum();
+            assert_eq!(covered as usize, 89 - unplaced);
+        }
+    }
+}
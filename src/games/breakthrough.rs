@@ -0,0 +1,364 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Cell, Position},
+        on_board, tuple_to_pos,
+    },
+    GameResult, GameState, Player,
+};
+
+/// Breakthrough on an 8x8 board. Each player starts with two rows of pawns, player 1 along the
+/// bottom and player 2 along the top.
+///
+/// Pawns move one square straight or diagonally forward, but can only capture diagonally. The
+/// first player to reach the far row, or to capture every enemy pawn, wins. There are no draws.
+#[derive(Clone)]
+pub struct Breakthrough
+{
+    board: Board<Cell>,
+    last_move: Option<BreakthroughMove>,
+}
+
+impl Breakthrough
+{
+    pub fn new() -> Breakthrough
+    {
+        let mut board = Board::new(8, 8);
+        for col in 0..8
+        {
+            for row in [0, 1]
+            {
+                board[Position { row, col }] = Cell::Piece(Player { id: 2 });
+            }
+            for row in [6, 7]
+            {
+                board[Position { row, col }] = Cell::Piece(Player { id: 1 });
+            }
+        }
+
+        Breakthrough {
+            board,
+            last_move: None,
+        }
+    }
+
+    fn forward(player: Player) -> i128
+    {
+        match player.id
+        {
+            1 => -1,
+            _ => 1,
+        }
+    }
+
+    fn goal_row(&self, player: Player) -> usize
+    {
+        match player.id
+        {
+            1 => 0,
+            _ => self.board.rows() - 1,
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position>
+    {
+        let cols = self.board.cols();
+        (0..self.board.rows()).flat_map(move |row| (0..cols).map(move |col| Position { row, col }))
+    }
+
+    // where the pawn on `from` would go when stepping forward and `side` columns across, or None
+    // if it would leave the board or the square can't be moved to
+    fn step(&self, player: Player, from: Position, side: i128) -> Option<Position>
+    {
+        let to = (
+            from.row as i128 + Breakthrough::forward(player),
+            from.col as i128 + side,
+        );
+        if !on_board(to, (self.board.rows(), self.board.cols()))
+        {
+            return None;
+        }
+
+        let to = tuple_to_pos(to);
+        let allowed = match self.board[to]
+        {
+            Cell::Empty => true,
+            // captures are only allowed diagonally
+            Cell::Piece(other) => other != player && side != 0,
+        };
+        allowed.then_some(to)
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<BreakthroughMove>
+    {
+        let mut moves = vec![];
+        for from in self.positions()
+        {
+            if self.board[from] != Cell::Piece(player)
+            {
+                continue;
+            }
+
+            for side in [-1, 0, 1]
+            {
+                if let Some(to) = self.step(player, from, side)
+                {
+                    moves.push(BreakthroughMove { from, to, player });
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+impl Default for Breakthrough
+{
+    fn default() -> Self
+    {
+        Breakthrough::new()
+    }
+}
+
+impl GameState for Breakthrough
+{
+    type Move = BreakthroughMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        self.moves_for(self.player_to_move())
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        let side = m.to.col as i128 - m.from.col as i128;
+        let valid = m.player == self.player_to_move()
+            && m.from.row < self.board.rows()
+            && m.from.col < self.board.cols()
+            && self.board[m.from] == Cell::Piece(m.player)
+            && (-1..=1).contains(&side)
+            && self.step(m.player, m.from, side) == Some(m.to);
+        if !valid
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board[m.to] = Cell::Piece(m.player);
+        self.board[m.from] = Cell::Empty;
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        for id in [1, 2]
+        {
+            let player = Player { id };
+            let goal_row = self.goal_row(player);
+            if (0..self.board.cols())
+                .any(|col| self.board[Position { row: goal_row, col }] == Cell::Piece(player))
+            {
+                return GameResult::Win(player);
+            }
+        }
+
+        // losing every pawn, or having every pawn blocked, loses the game
+        let player_to_move = self.player_to_move();
+        if self.moves_for(player_to_move).is_empty()
+        {
+            return GameResult::Win(Player {
+                id: if player_to_move.id == 1 { 2 } else { 1 },
+            });
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Breakthrough
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct BreakthroughMove
+{
+    from: Position,
+    to: Position,
+    player: Player,
+}
+
+impl Display for BreakthroughMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, From: {}, To: {}", self.player, self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::board::{Board, Cell, Position},
+            GameResult, GameState, Player,
+        },
+        players::{minimax::MinimaxPlayer, random::RandomPlayer},
+    };
+
+    use super::{Breakthrough, BreakthroughMove};
+
+    #[test]
+    fn test_moves_and_captures()
+    {
+        let game = Breakthrough::new();
+        // the 6 middle pawns have 3 moves each and the 2 edge pawns have 2
+        assert_eq!(game.get_valid_moves().len(), 6 * 3 + 2 * 2);
+
+        let mut game = Breakthrough {
+            board: Board::new(8, 8),
+            last_move: None,
+        };
+        game.board[Position { row: 4, col: 4 }] = Cell::Piece(Player::new(1));
+        game.board[Position { row: 3, col: 4 }] = Cell::Piece(Player::new(2));
+        game.board[Position { row: 3, col: 5 }] = Cell::Piece(Player::new(2));
+
+        // the pawn straight ahead blocks, but the diagonal one can be captured
+        let moves = game.get_valid_moves();
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().all(|m| m.to.col != 4));
+
+        let game = game.do_move(BreakthroughMove {
+            from: Position { row: 4, col: 4 },
+            to: Position { row: 3, col: 5 },
+            player: Player::new(1),
+        });
+        assert!(game.board[Position { row: 3, col: 5 }] == Cell::Piece(Player::new(1)));
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+
+    fn step(game: Breakthrough, from: (usize, usize), to: (usize, usize)) -> Breakthrough
+    {
+        game.do_move(BreakthroughMove {
+            from: Position {
+                row: from.0,
+                col: from.1,
+            },
+            to: Position {
+                row: to.0,
+                col: to.1,
+            },
+            player: Player::new(1),
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_capturing_straight_ahead()
+    {
+        let mut game = Breakthrough {
+            board: Board::new(8, 8),
+            last_move: None,
+        };
+        game.board[Position { row: 4, col: 4 }] = Cell::Piece(Player::new(1));
+        game.board[Position { row: 3, col: 4 }] = Cell::Piece(Player::new(2));
+        step(game, (4, 4), (3, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_moving_two_squares()
+    {
+        step(Breakthrough::new(), (6, 3), (4, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_moving_sideways()
+    {
+        let mut game = Breakthrough {
+            board: Board::new(8, 8),
+            last_move: None,
+        };
+        game.board[Position { row: 4, col: 4 }] = Cell::Piece(Player::new(1));
+        step(game, (4, 4), (4, 5));
+    }
+
+    #[test]
+    fn test_reaching_far_row()
+    {
+        let mut game = Breakthrough {
+            board: Board::new(8, 8),
+            last_move: None,
+        };
+        game.board[Position { row: 3, col: 0 }] = Cell::Piece(Player::new(1));
+        game.board[Position { row: 4, col: 7 }] = Cell::Piece(Player::new(2));
+
+        // both pawns are three moves from the far row, so player 1 wins the race by moving first
+        let result = game.play(
+            &mut MinimaxPlayer::new(None),
+            &mut MinimaxPlayer::new(None),
+            false,
+        );
+        assert!(result == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    fn test_no_draws()
+    {
+        for seed in 0..10
+        {
+            let result = Breakthrough::new().play(
+                &mut RandomPlayer::from_seed(seed),
+                &mut RandomPlayer::from_seed(seed + 100),
+                false,
+            );
+            assert!(result != GameResult::Draw);
+        }
+    }
+}
@@ -0,0 +1,965 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Position},
+        on_board, tuple_to_pos,
+    },
+    GameResult, GameState, Player,
+};
+
+pub mod notation;
+
+const WHITE: Player = Player { id: 1 };
+const BLACK: Player = Player { id: 2 };
+
+// indices into Chess::castling
+const WHITE_KINGSIDE: usize = 0;
+const WHITE_QUEENSIDE: usize = 1;
+const BLACK_KINGSIDE: usize = 2;
+const BLACK_QUEENSIDE: usize = 3;
+
+const KNIGHT_OFFSETS: [(i128, i128); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+const KING_OFFSETS: [(i128, i128); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const ROOK_DIRS: [(i128, i128); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const BISHOP_DIRS: [(i128, i128); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+/// Standard chess. Player 1 is white and player 2 is black.
+///
+/// Rows are stored top to bottom as they are printed, so row 0 is the 8th rank
+/// and column 0 is the a-file.
+#[derive(Clone)]
+pub struct Chess
+{
+    board: Board<Square>,
+    to_move: Player,
+    castling: [bool; 4],
+    en_passant: Option<Position>,
+    halfmove_clock: usize,
+    fullmove_number: usize,
+    // positions since the last irreversible move, used to detect threefold repetition
+    history: Vec<PositionKey>,
+    last_move: Option<ChessMove>,
+}
+
+impl Chess
+{
+    pub fn new() -> Chess
+    {
+        Chess::from_fen(notation::START_FEN).expect("starting position should be valid")
+    }
+
+    pub fn in_check(&self) -> bool
+    {
+        match find_king(&self.board, self.to_move)
+        {
+            Some(king) => is_attacked(&self.board, king, opponent(self.to_move)),
+            None => false,
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<ChessMove>
+    {
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|&m| {
+                let mut board = self.board.clone();
+                move_piece(&mut board, m, self.en_passant);
+                match find_king(&board, self.to_move)
+                {
+                    Some(king) => !is_attacked(&board, king, opponent(self.to_move)),
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    fn pseudo_legal_moves(&self) -> Vec<ChessMove>
+    {
+        let mut moves = vec![];
+
+        for row in 0..8
+        {
+            for col in 0..8
+            {
+                let from = Position { row, col };
+                let piece = match self.board[from]
+                {
+                    Square::Piece(p) if p.player == self.to_move => p,
+                    _ => continue,
+                };
+
+                match piece.kind
+                {
+                    PieceKind::Pawn => self.pawn_moves(from, &mut moves),
+                    PieceKind::Knight => self.step_moves(from, &KNIGHT_OFFSETS, &mut moves),
+                    PieceKind::Bishop => self.slide_moves(from, &BISHOP_DIRS, &mut moves),
+                    PieceKind::Rook => self.slide_moves(from, &ROOK_DIRS, &mut moves),
+                    PieceKind::Queen =>
+                    {
+                        self.slide_moves(from, &ROOK_DIRS, &mut moves);
+                        self.slide_moves(from, &BISHOP_DIRS, &mut moves);
+                    },
+                    PieceKind::King =>
+                    {
+                        self.step_moves(from, &KING_OFFSETS, &mut moves);
+                        self.castling_moves(from, &mut moves);
+                    },
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn pawn_moves(&self, from: Position, moves: &mut Vec<ChessMove>)
+    {
+        let (forward, start_row, last_row) = match self.to_move
+        {
+            WHITE => (-1, 6, 0),
+            _ => (1, 1, 7),
+        };
+
+        let mut push = |to: Position| {
+            if to.row == last_row
+            {
+                for kind in [
+                    PieceKind::Queen,
+                    PieceKind::Rook,
+                    PieceKind::Bishop,
+                    PieceKind::Knight,
+                ]
+                {
+                    moves.push(ChessMove {
+                        from,
+                        to,
+                        promotion: Some(kind),
+                    });
+                }
+            }
+            else
+            {
+                moves.push(ChessMove {
+                    from,
+                    to,
+                    promotion: None,
+                });
+            }
+        };
+
+        let row = from.row as i128;
+        let col = from.col as i128;
+
+        let one = (row + forward, col);
+        if on_board(one, (8, 8)) && self.board[tuple_to_pos(one)] == Square::Empty
+        {
+            push(tuple_to_pos(one));
+
+            let two = (row + 2 * forward, col);
+            if from.row == start_row && self.board[tuple_to_pos(two)] == Square::Empty
+            {
+                push(tuple_to_pos(two));
+            }
+        }
+
+        for side in [-1, 1]
+        {
+            let target = (row + forward, col + side);
+            if !on_board(target, (8, 8))
+            {
+                continue;
+            }
+
+            let to = tuple_to_pos(target);
+            let is_capture = match self.board[to]
+            {
+                Square::Piece(p) => p.player != self.to_move,
+                Square::Empty => self.en_passant == Some(to),
+            };
+            if is_capture
+            {
+                push(to);
+            }
+        }
+    }
+
+    fn step_moves(&self, from: Position, offsets: &[(i128, i128)], moves: &mut Vec<ChessMove>)
+    {
+        for offset in offsets
+        {
+            let target = (from.row as i128 + offset.0, from.col as i128 + offset.1);
+            if !on_board(target, (8, 8))
+            {
+                continue;
+            }
+
+            let to = tuple_to_pos(target);
+            if self.can_land_on(to)
+            {
+                moves.push(ChessMove {
+                    from,
+                    to,
+                    promotion: None,
+                });
+            }
+        }
+    }
+
+    fn slide_moves(&self, from: Position, dirs: &[(i128, i128)], moves: &mut Vec<ChessMove>)
+    {
+        for dir in dirs
+        {
+            let mut target = (from.row as i128 + dir.0, from.col as i128 + dir.1);
+            while on_board(target, (8, 8))
+            {
+                let to = tuple_to_pos(target);
+                if self.can_land_on(to)
+                {
+                    moves.push(ChessMove {
+                        from,
+                        to,
+                        promotion: None,
+                    });
+                }
+
+                // stop sliding once we hit any piece
+                if self.board[to] != Square::Empty
+                {
+                    break;
+                }
+
+                target.0 += dir.0;
+                target.1 += dir.1;
+            }
+        }
+    }
+
+    fn castling_moves(&self, from: Position, moves: &mut Vec<ChessMove>)
+    {
+        let (home_row, kingside, queenside) = match self.to_move
+        {
+            WHITE => (7, WHITE_KINGSIDE, WHITE_QUEENSIDE),
+            _ => (0, BLACK_KINGSIDE, BLACK_QUEENSIDE),
+        };
+
+        if from
+            != (Position {
+                row: home_row,
+                col: 4,
+            })
+            || self.in_check()
+        {
+            return;
+        }
+
+        let rook = Square::Piece(Piece {
+            kind: PieceKind::Rook,
+            player: self.to_move,
+        });
+        let empty = |cols: &[usize]| {
+            cols.iter()
+                .all(|&col| self.board[Position { row: home_row, col }] == Square::Empty)
+        };
+        let safe = |col: usize| {
+            !is_attacked(
+                &self.board,
+                Position { row: home_row, col },
+                opponent(self.to_move),
+            )
+        };
+
+        // the destination square is checked along with every other move in legal_moves
+        if self.castling[kingside]
+            && self.board[Position {
+                row: home_row,
+                col: 7,
+            }] == rook
+            && empty(&[5, 6])
+            && safe(5)
+        {
+            moves.push(ChessMove {
+                from,
+                to: Position {
+                    row: home_row,
+                    col: 6,
+                },
+                promotion: None,
+            });
+        }
+        if self.castling[queenside]
+            && self.board[Position {
+                row: home_row,
+                col: 0,
+            }] == rook
+            && empty(&[1, 2, 3])
+            && safe(3)
+        {
+            moves.push(ChessMove {
+                from,
+                to: Position {
+                    row: home_row,
+                    col: 2,
+                },
+                promotion: None,
+            });
+        }
+    }
+
+    fn can_land_on(&self, pos: Position) -> bool
+    {
+        match self.board[pos]
+        {
+            Square::Empty => true,
+            Square::Piece(p) => p.player != self.to_move,
+        }
+    }
+
+    fn position_key(&self) -> PositionKey
+    {
+        let mut squares = [0; 64];
+        for row in 0..8
+        {
+            for col in 0..8
+            {
+                squares[row * 8 + col] = self.board[Position { row, col }].code();
+            }
+        }
+
+        // an en passant square only makes positions different if the capture is actually possible
+        let en_passant = self.en_passant.filter(|_| {
+            self.pseudo_legal_moves()
+                .iter()
+                .any(|m| Some(m.to) == self.en_passant && self.is_pawn(m.from))
+        });
+
+        PositionKey {
+            squares,
+            to_move: self.to_move,
+            castling: self.castling,
+            en_passant,
+        }
+    }
+
+    fn is_pawn(&self, pos: Position) -> bool
+    {
+        matches!(
+            self.board[pos],
+            Square::Piece(Piece {
+                kind: PieceKind::Pawn,
+                ..
+            })
+        )
+    }
+
+    fn is_threefold_repetition(&self) -> bool
+    {
+        match self.history.last()
+        {
+            Some(current) => self.history.iter().filter(|&k| k == current).count() >= 3,
+            None => false,
+        }
+    }
+
+    fn is_insufficient_material(&self) -> bool
+    {
+        // (piece kind, square colour) of every piece other than the kings
+        let mut pieces = vec![];
+        for row in 0..8
+        {
+            for col in 0..8
+            {
+                if let Square::Piece(p) = self.board[Position { row, col }]
+                {
+                    if p.kind != PieceKind::King
+                    {
+                        pieces.push((p.kind, (row + col) % 2));
+                    }
+                }
+            }
+        }
+
+        match pieces.as_slice()
+        {
+            [] | [(PieceKind::Knight, _)] | [(PieceKind::Bishop, _)] => true,
+            [(_, colour), ..] => pieces
+                .iter()
+                .all(|&(kind, c)| kind == PieceKind::Bishop && c == *colour),
+        }
+    }
+
+    fn is_draw_by_rule(&self) -> bool
+    {
+        self.halfmove_clock >= 100
+            || self.is_threefold_repetition()
+            || self.is_insufficient_material()
+    }
+}
+
+impl Default for Chess
+{
+    fn default() -> Self
+    {
+        Chess::new()
+    }
+}
+
+impl GameState for Chess
+{
+    type Move = ChessMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.is_draw_by_rule()
+        {
+            return vec![];
+        }
+
+        self.legal_moves()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        let piece = match self.board[m.from]
+        {
+            Square::Piece(p) => p,
+            Square::Empty => panic!("the move: {m} was not a valid move"),
+        };
+
+        let is_capture = self.board[m.to] != Square::Empty
+            || (piece.kind == PieceKind::Pawn && Some(m.to) == self.en_passant);
+
+        move_piece(&mut self.board, m, self.en_passant);
+
+        // moving the king or a rook, or capturing a rook on its starting square, loses castling rights
+        for (pos, right) in [
+            (Position { row: 7, col: 7 }, WHITE_KINGSIDE),
+            (Position { row: 7, col: 0 }, WHITE_QUEENSIDE),
+            (Position { row: 0, col: 7 }, BLACK_KINGSIDE),
+            (Position { row: 0, col: 0 }, BLACK_QUEENSIDE),
+        ]
+        {
+            if m.from == pos || m.to == pos
+            {
+                self.castling[right] = false;
+            }
+        }
+        if piece.kind == PieceKind::King
+        {
+            match piece.player
+            {
+                WHITE =>
+                {
+                    self.castling[WHITE_KINGSIDE] = false;
+                    self.castling[WHITE_QUEENSIDE] = false;
+                },
+                _ =>
+                {
+                    self.castling[BLACK_KINGSIDE] = false;
+                    self.castling[BLACK_QUEENSIDE] = false;
+                },
+            }
+        }
+
+        self.en_passant = None;
+        if piece.kind == PieceKind::Pawn && m.from.row.abs_diff(m.to.row) == 2
+        {
+            self.en_passant = Some(Position {
+                row: (m.from.row + m.to.row) / 2,
+                col: m.from.col,
+            });
+        }
+
+        if piece.kind == PieceKind::Pawn || is_capture
+        {
+            self.halfmove_clock = 0;
+            // earlier positions can never be repeated after an irreversible move
+            self.history.clear();
+        }
+        else
+        {
+            self.halfmove_clock += 1;
+        }
+
+        if self.to_move == BLACK
+        {
+            self.fullmove_number += 1;
+        }
+        self.to_move = opponent(self.to_move);
+        self.last_move = Some(m);
+
+        let key = self.position_key();
+        self.history.push(key);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if self.legal_moves().is_empty()
+        {
+            return match self.in_check()
+            {
+                true => GameResult::Win(opponent(self.to_move)),
+                false => GameResult::Draw,
+            };
+        }
+
+        if self.is_draw_by_rule()
+        {
+            return GameResult::Draw;
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Chess
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        writeln!(f, "FEN: {}", self.to_fen())?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+/// A move from one square to another, written in UCI notation (e.g. `e2e4`, `e7e8q`).
+/// Castling is written as the king moving two squares.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ChessMove
+{
+    from: Position,
+    to: Position,
+    promotion: Option<PieceKind>,
+}
+
+impl Display for ChessMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}{}",
+            notation::square_name(self.from),
+            notation::square_name(self.to)
+        )?;
+        if let Some(kind) = self.promotion
+        {
+            write!(f, "{}", kind.letter().to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PieceKind
+{
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind
+{
+    fn letter(&self) -> char
+    {
+        match self
+        {
+            PieceKind::Pawn => 'P',
+            PieceKind::Knight => 'N',
+            PieceKind::Bishop => 'B',
+            PieceKind::Rook => 'R',
+            PieceKind::Queen => 'Q',
+            PieceKind::King => 'K',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<PieceKind>
+    {
+        match letter.to_ascii_uppercase()
+        {
+            'P' => Some(PieceKind::Pawn),
+            'N' => Some(PieceKind::Knight),
+            'B' => Some(PieceKind::Bishop),
+            'R' => Some(PieceKind::Rook),
+            'Q' => Some(PieceKind::Queen),
+            'K' => Some(PieceKind::King),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Piece
+{
+    kind: PieceKind,
+    player: Player,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Square
+{
+    #[default]
+    Empty,
+    Piece(Piece),
+}
+
+impl Square
+{
+    fn code(&self) -> u8
+    {
+        match self
+        {
+            Square::Empty => 0,
+            Square::Piece(p) => 1 + p.kind as u8 + 6 * (p.player.id as u8 - 1),
+        }
+    }
+}
+
+impl Display for Square
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Square::Empty => write!(f, "-"),
+            Square::Piece(p) if p.player == WHITE => write!(f, "{}", p.kind.letter()),
+            Square::Piece(p) => write!(f, "{}", p.kind.letter().to_ascii_lowercase()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct PositionKey
+{
+    squares: [u8; 64],
+    to_move: Player,
+    castling: [bool; 4],
+    en_passant: Option<Position>,
+}
+
+fn opponent(player: Player) -> Player
+{
+    match player
+    {
+        WHITE => BLACK,
+        _ => WHITE,
+    }
+}
+
+fn find_king(board: &Board<Square>, player: Player) -> Option<Position>
+{
+    let king = Square::Piece(Piece {
+        kind: PieceKind::King,
+        player,
+    });
+
+    (0..8)
+        .flat_map(|row| (0..8).map(move |col| Position { row, col }))
+        .find(|&pos| board[pos] == king)
+}
+
+/// Returns whether any piece belonging to `by` attacks `pos`
+fn is_attacked(board: &Board<Square>, pos: Position, by: Player) -> bool
+{
+    let row = pos.row as i128;
+    let col = pos.col as i128;
+
+    let holds = |target: (i128, i128), kinds: &[PieceKind]| {
+        on_board(target, (8, 8))
+            && match board[tuple_to_pos(target)]
+            {
+                Square::Piece(p) => p.player == by && kinds.contains(&p.kind),
+                Square::Empty => false,
+            }
+    };
+
+    // pawns attack towards the opposite side of the board, so look back the way they came
+    let pawn_row = match by
+    {
+        WHITE => row + 1,
+        _ => row - 1,
+    };
+    if holds((pawn_row, col - 1), &[PieceKind::Pawn])
+        || holds((pawn_row, col + 1), &[PieceKind::Pawn])
+    {
+        return true;
+    }
+
+    if KNIGHT_OFFSETS
+        .iter()
+        .any(|o| holds((row + o.0, col + o.1), &[PieceKind::Knight]))
+        || KING_OFFSETS
+            .iter()
+            .any(|o| holds((row + o.0, col + o.1), &[PieceKind::King]))
+    {
+        return true;
+    }
+
+    for (dirs, kinds) in [
+        (ROOK_DIRS, [PieceKind::Rook, PieceKind::Queen]),
+        (BISHOP_DIRS, [PieceKind::Bishop, PieceKind::Queen]),
+    ]
+    {
+        for dir in dirs
+        {
+            let mut target = (row + dir.0, col + dir.1);
+            while on_board(target, (8, 8))
+            {
+                match board[tuple_to_pos(target)]
+                {
+                    Square::Empty =>
+                    {},
+                    Square::Piece(p) if p.player == by && kinds.contains(&p.kind) => return true,
+                    Square::Piece(_) => break,
+                }
+                target.0 += dir.0;
+                target.1 += dir.1;
+            }
+        }
+    }
+
+    false
+}
+
+/// Moves the pieces on `board` for `m`, including the rook when castling and the captured pawn
+/// when capturing en passant. Does not check that the move is legal.
+fn move_piece(board: &mut Board<Square>, m: ChessMove, en_passant: Option<Position>)
+{
+    let piece = match board[m.from]
+    {
+        Square::Piece(p) => p,
+        Square::Empty => panic!("the move: {m} was not a valid move"),
+    };
+
+    if piece.kind == PieceKind::Pawn
+        && Some(m.to) == en_passant
+        && m.from.col != m.to.col
+        && board[m.to] == Square::Empty
+    {
+        board[Position {
+            row: m.from.row,
+            col: m.to.col,
+        }] = Square::Empty;
+    }
+
+    if piece.kind == PieceKind::King && m.from.col.abs_diff(m.to.col) == 2
+    {
+        let (rook_from, rook_to) = match m.to.col
+        {
+            6 => (7, 5),
+            _ => (0, 3),
+        };
+        board[Position {
+            row: m.from.row,
+            col: rook_to,
+        }] = board[Position {
+            row: m.from.row,
+            col: rook_from,
+        }];
+        board[Position {
+            row: m.from.row,
+            col: rook_from,
+        }] = Square::Empty;
+    }
+
+    board[m.from] = Square::Empty;
+    board[m.to] = Square::Piece(match m.promotion
+    {
+        Some(kind) => Piece {
+            kind,
+            player: piece.player,
+        },
+        None => piece,
+    });
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{GameResult, GameState, Player};
+
+    use super::Chess;
+
+    fn perft(state: &Chess, depth: usize) -> usize
+    {
+        let moves = state.legal_moves();
+        if depth == 1
+        {
+            return moves.len();
+        }
+
+        moves
+            .into_iter()
+            .map(|m| perft(&state.clone().do_move(m), depth - 1))
+            .sum()
+    }
+
+    fn assert_perft(fen: &str, expected: &[usize])
+    {
+        let game = Chess::from_fen(fen).expect("invalid fen");
+        for (depth, &nodes) in expected.iter().enumerate()
+        {
+            assert_eq!(
+                perft(&game, depth + 1),
+                nodes,
+                "perft({}) of {fen}",
+                depth + 1
+            );
+        }
+    }
+
+    fn play_san(moves: &[&str]) -> Chess
+    {
+        let mut game = Chess::new();
+        for san in moves
+        {
+            let m = game.parse_san(san).expect("invalid move");
+            game = game.do_move(m);
+        }
+        game
+    }
+
+    #[test]
+    fn test_perft_start_position()
+    {
+        assert_perft(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            &[20, 400, 8902, 197281],
+        );
+    }
+
+    #[test]
+    fn test_perft_kiwipete()
+    {
+        assert_perft(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            &[48, 2039, 97862],
+        );
+    }
+
+    #[test]
+    fn test_perft_endgame()
+    {
+        assert_perft(
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            &[14, 191, 2812, 43238],
+        );
+    }
+
+    #[test]
+    fn test_perft_promotions()
+    {
+        assert_perft(
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            &[6, 264, 9467],
+        );
+        assert_perft(
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            &[44, 1486, 62379],
+        );
+    }
+
+    #[test]
+    fn test_checkmate()
+    {
+        let game = play_san(&["f3", "e5", "g4", "Qh4#"]);
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    fn test_draws()
+    {
+        let stalemate = Chess::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(stalemate.check_win() == GameResult::Draw);
+
+        let fifty_moves = Chess::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80").unwrap();
+        assert!(fifty_moves.check_win() == GameResult::Draw);
+
+        let insufficient = Chess::from_fen("4k3/8/8/2b5/8/4B3/8/4K3 w - - 0 1").unwrap();
+        assert!(insufficient.check_win() == GameResult::Draw);
+        let sufficient = Chess::from_fen("4k3/8/8/3b4/8/4B3/8/4K3 w - - 0 1").unwrap();
+        assert!(sufficient.check_win() == GameResult::InProgress);
+
+        let repetition = play_san(&["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1"]);
+        assert!(repetition.check_win() == GameResult::InProgress);
+        let m = repetition.parse_san("Ng8").unwrap();
+        let repetition = repetition.do_move(m);
+        assert!(repetition.check_win() == GameResult::Draw);
+    }
+
+    #[test]
+    fn test_notation()
+    {
+        let game =
+            Chess::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+
+        for m in game.get_valid_moves()
+        {
+            let san = game.to_san(m);
+            assert!(game.parse_san(&san) == Some(m), "{san} did not round trip");
+            assert!(
+                game.parse_uci(&m.to_string()) == Some(m),
+                "{m} did not round trip"
+            );
+        }
+
+        assert!(game.parse_san("O-O").map(|m| m.to_string()) == Some(String::from("e1g1")));
+        assert!(game.parse_san("Nxf7").map(|m| m.to_string()) == Some(String::from("e5f7")));
+        assert!(game.parse_san("Nd5").is_none());
+        assert!(game.parse_uci("e1e3").is_none());
+    }
+
+    #[test]
+    fn test_malformed_san()
+    {
+        let game = Chess::new();
+        for text in ["", "N", "Né4", "é4", "e9", "Zf3", "e8=", "O-O-O-O", "Nf3é"]
+        {
+            assert!(game.parse_san(text).is_none(), "{text} was parsed");
+        }
+        assert!(game.parse_san("Nf3").is_some());
+    }
+}
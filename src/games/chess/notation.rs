@@ -0,0 +1,351 @@
+use crate::games::{
+    common::board::{Board, Position},
+    GameResult, GameState,
+};
+
+use super::{
+    Chess, ChessMove, Piece, PieceKind, Square, BLACK, BLACK_KINGSIDE, BLACK_QUEENSIDE, WHITE,
+    WHITE_KINGSIDE, WHITE_QUEENSIDE,
+};
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+impl Chess
+{
+    /// Creates a game from a position in Forsyth-Edwards Notation. The move counters are optional.
+    pub fn from_fen(fen: &str) -> Option<Chess>
+    {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() < 4
+        {
+            return None;
+        }
+
+        let mut game = Chess {
+            board: Board::new(8, 8),
+            to_move: WHITE,
+            castling: [false; 4],
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            history: vec![],
+            last_move: None,
+        };
+
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8
+        {
+            return None;
+        }
+        for (row, rank) in ranks.iter().enumerate()
+        {
+            let mut col = 0;
+            for c in rank.chars()
+            {
+                if let Some(skip) = c.to_digit(10)
+                {
+                    col += skip as usize;
+                    continue;
+                }
+
+                if col >= 8
+                {
+                    return None;
+                }
+                let player = if c.is_ascii_uppercase() { WHITE } else { BLACK };
+                let kind = PieceKind::from_letter(c)?;
+                game.board[Position { row, col }] = Square::Piece(Piece { kind, player });
+                col += 1;
+            }
+
+            if col != 8
+            {
+                return None;
+            }
+        }
+
+        game.to_move = match fields[1]
+        {
+            "w" => WHITE,
+            "b" => BLACK,
+            _ => return None,
+        };
+
+        if fields[2] != "-"
+        {
+            for c in fields[2].chars()
+            {
+                let right = match c
+                {
+                    'K' => WHITE_KINGSIDE,
+                    'Q' => WHITE_QUEENSIDE,
+                    'k' => BLACK_KINGSIDE,
+                    'q' => BLACK_QUEENSIDE,
+                    _ => return None,
+                };
+                game.castling[right] = true;
+            }
+        }
+
+        if fields[3] != "-"
+        {
+            game.en_passant = Some(parse_square(fields[3])?);
+        }
+
+        if let Some(halfmove_clock) = fields.get(4)
+        {
+            game.halfmove_clock = halfmove_clock.parse().ok()?;
+        }
+        if let Some(fullmove_number) = fields.get(5)
+        {
+            game.fullmove_number = fullmove_number.parse().ok()?;
+        }
+
+        let key = game.position_key();
+        game.history.push(key);
+
+        Some(game)
+    }
+
+    pub fn to_fen(&self) -> String
+    {
+        let mut fen = String::new();
+
+        for row in 0..8
+        {
+            let mut empty = 0;
+            for col in 0..8
+            {
+                match self.board[Position { row, col }]
+                {
+                    Square::Empty => empty += 1,
+                    square =>
+                    {
+                        if empty > 0
+                        {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push_str(&square.to_string());
+                    },
+                }
+            }
+            if empty > 0
+            {
+                fen.push_str(&empty.to_string());
+            }
+            if row < 7
+            {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(if self.to_move == WHITE { " w " } else { " b " });
+
+        let castling: String = "KQkq"
+            .chars()
+            .zip(self.castling)
+            .filter(|(_, allowed)| *allowed)
+            .map(|(c, _)| c)
+            .collect();
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        match self.en_passant
+        {
+            Some(pos) => fen.push_str(&format!(" {}", square_name(pos))),
+            None => fen.push_str(" -"),
+        }
+
+        fen.push_str(&format!(
+            " {} {}",
+            self.halfmove_clock, self.fullmove_number
+        ));
+
+        fen
+    }
+
+    /// Finds the legal move written in UCI notation, e.g. `e2e4` or `e7e8q`
+    pub fn parse_uci(&self, text: &str) -> Option<ChessMove>
+    {
+        self.get_valid_moves()
+            .into_iter()
+            .find(|m| m.to_string() == text.trim())
+    }
+
+    /// Finds the legal move written in Standard Algebraic Notation, e.g. `Nf3`, `exd6`, `O-O` or `e8=Q+`
+    pub fn parse_san(&self, text: &str) -> Option<ChessMove>
+    {
+        let text = text.trim().trim_end_matches(['+', '#', '!', '?']);
+        // SAN is all ASCII, and the parsing below slices by byte
+        if !text.is_ascii()
+        {
+            return None;
+        }
+
+        let moves = self.get_valid_moves();
+
+        let castle_col = match text
+        {
+            "O-O" | "0-0" => Some(6),
+            "O-O-O" | "0-0-0" => Some(2),
+            _ => None,
+        };
+        if let Some(col) = castle_col
+        {
+            return moves.into_iter().find(|m| {
+                self.kind_at(m.from) == Some(PieceKind::King) && m.from.col == 4 && m.to.col == col
+            });
+        }
+
+        let (text, promotion) = match text.split_once('=')
+        {
+            Some((body, piece)) => (body, Some(PieceKind::from_letter(piece.chars().next()?)?)),
+            None => (text, None),
+        };
+
+        let (kind, text) = match text.chars().next()
+        {
+            Some(c) if c.is_ascii_uppercase() => (PieceKind::from_letter(c)?, &text[1..]),
+            _ => (PieceKind::Pawn, text),
+        };
+
+        let text = text.replace('x', "");
+        if text.len() < 2
+        {
+            return None;
+        }
+        let (disambiguation, destination) = text.split_at(text.len() - 2);
+        let to = parse_square(destination)?;
+
+        let mut candidates = moves.into_iter().filter(|m| {
+            self.kind_at(m.from) == Some(kind)
+                && m.to == to
+                && m.promotion == promotion
+                && disambiguation.chars().all(|c| match c
+                {
+                    'a'..='h' => m.from.col == c as usize - 'a' as usize,
+                    '1'..='8' => m.from.row == 8 - c.to_digit(10).unwrap() as usize,
+                    _ => false,
+                })
+        });
+
+        // ambiguous moves are not accepted
+        match (candidates.next(), candidates.next())
+        {
+            (Some(m), None) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// Writes a legal move in Standard Algebraic Notation
+    pub fn to_san(&self, m: ChessMove) -> String
+    {
+        let kind = self
+            .kind_at(m.from)
+            .expect("no piece on the square moved from");
+        let is_capture = self.board[m.to] != Square::Empty
+            || (kind == PieceKind::Pawn && m.from.col != m.to.col);
+
+        let mut san = String::new();
+        if kind == PieceKind::King && m.from.col.abs_diff(m.to.col) == 2
+        {
+            san.push_str(if m.to.col == 6 { "O-O" } else { "O-O-O" });
+        }
+        else
+        {
+            if kind == PieceKind::Pawn
+            {
+                if is_capture
+                {
+                    san.push_str(&square_name(m.from)[..1]);
+                }
+            }
+            else
+            {
+                san.push(kind.letter());
+
+                // other pieces of the same kind that could also move to the same square
+                let others: Vec<Position> = self
+                    .get_valid_moves()
+                    .into_iter()
+                    .filter(|o| {
+                        o.to == m.to && o.from != m.from && self.kind_at(o.from) == Some(kind)
+                    })
+                    .map(|o| o.from)
+                    .collect();
+
+                let name = square_name(m.from);
+                if !others.is_empty()
+                {
+                    if others.iter().all(|o| o.col != m.from.col)
+                    {
+                        san.push_str(&name[..1]);
+                    }
+                    else if others.iter().all(|o| o.row != m.from.row)
+                    {
+                        san.push_str(&name[1..]);
+                    }
+                    else
+                    {
+                        san.push_str(&name);
+                    }
+                }
+            }
+
+            if is_capture
+            {
+                san.push('x');
+            }
+            san.push_str(&square_name(m.to));
+
+            if let Some(promotion) = m.promotion
+            {
+                san.push('=');
+                san.push(promotion.letter());
+            }
+        }
+
+        let after = self.clone().do_move(m);
+        if after.in_check()
+        {
+            match after.check_win()
+            {
+                GameResult::Win(_) => san.push('#'),
+                _ => san.push('+'),
+            }
+        }
+
+        san
+    }
+
+    fn kind_at(&self, pos: Position) -> Option<PieceKind>
+    {
+        match self.board[pos]
+        {
+            Square::Piece(p) => Some(p.kind),
+            Square::Empty => None,
+        }
+    }
+}
+
+pub(super) fn square_name(pos: Position) -> String
+{
+    format!("{}{}", (b'a' + pos.col as u8) as char, 8 - pos.row)
+}
+
+pub(super) fn parse_square(text: &str) -> Option<Position>
+{
+    let mut chars = text.chars();
+    let file = chars.next()?;
+    let rank = chars.next()?.to_digit(10)? as usize;
+
+    if chars.next().is_some() || !('a'..='h').contains(&file) || !(1..=8).contains(&rank)
+    {
+        return None;
+    }
+
+    Some(Position {
+        row: 8 - rank,
+        col: file as usize - 'a' as usize,
+    })
+}
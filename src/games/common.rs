@@ -1,5 +1,5 @@
-use super::GameState;
-use board::Position;
+use super::{GameState, Player};
+use board::{Board, Cell, Position};
 
 use petgraph::dot::Dot;
 use petgraph::graph::NodeIndex;
@@ -12,6 +12,9 @@ use std::io::BufWriter;
 use std::io::Write;
 
 pub mod board;
+pub mod board3d;
+pub mod cgt;
+pub mod grundy;
 
 // TODO: accept parent pointer so graph can be added to another graph
 pub fn create_game_tree<T>(state: &T, depth: Option<usize>) -> Graph<T, T::Move>
@@ -88,6 +91,216 @@ pub fn generate_line(pos: Position, dir: (i128, i128), size: (usize, usize)) ->
     positions.into_iter().collect()
 }
 
+/// `generate_line` for boards whose edges can wrap around. Returns the positions up to
+/// `length - 1` steps either side of `pos` in order, stopping at any edge that doesn't wrap.
+///
+/// If the line comes back around to `pos` in fewer than `length` steps there is no room for
+/// `length` different positions in a row, so nothing is returned.
+pub fn generate_wrapped_line(
+    pos: Position,
+    dir: (i128, i128),
+    size: (usize, usize),
+    topology: Topology,
+    length: usize,
+) -> Vec<Position>
+{
+    let (wrap_rows, wrap_cols) = topology.wraps();
+
+    // lines that only move along edges that wrap eventually repeat themselves
+    let rows: i128 = size.0.try_into().unwrap();
+    let cols: i128 = size.1.try_into().unwrap();
+    let row_period = if dir.0 == 0
+    {
+        Some(1)
+    }
+    else
+    {
+        wrap_rows.then_some(rows)
+    };
+    let col_period = if dir.1 == 0
+    {
+        Some(1)
+    }
+    else
+    {
+        wrap_cols.then_some(cols)
+    };
+    if let (Some(row_period), Some(col_period)) = (row_period, col_period)
+    {
+        let period = lcm(row_period, col_period);
+        if period < length.try_into().unwrap()
+        {
+            return vec![];
+        }
+    }
+
+    let start_pos: (i128, i128) = (pos.row.try_into().unwrap(), pos.col.try_into().unwrap());
+    let steps: i128 = (length - 1).try_into().unwrap();
+    let at = |i: i128| {
+        wrap_position(
+            (start_pos.0 + i * dir.0, start_pos.1 + i * dir.1),
+            size,
+            topology,
+        )
+    };
+
+    let mut positions = VecDeque::from([pos]);
+    for i in 1..=steps
+    {
+        match at(-i)
+        {
+            Some(p) => positions.push_front(p),
+            None => break,
+        }
+    }
+    for i in 1..=steps
+    {
+        match at(i)
+        {
+            Some(p) => positions.push_back(p),
+            None => break,
+        }
+    }
+
+    positions.into_iter().collect()
+}
+
+/// Scores `board` for `player` in a game of getting `length` in a row. Every window of `length`
+/// cells in a row that only one player has pieces in is open for that player, and is worth n^2 for
+/// n pieces. The score is the value of `player`'s open windows minus the other player's.
+pub fn evaluate_windows(
+    board: &Board<Cell>,
+    topology: Topology,
+    length: usize,
+    player: Player,
+) -> f32
+{
+    let size = (board.rows(), board.cols());
+    let steps: i128 = length.try_into().unwrap();
+
+    let mut score = 0.0;
+    for row in 0..board.rows()
+    {
+        for col in 0..board.cols()
+        {
+            for dir in [(0, 1), (1, 0), (1, 1), (1, -1)]
+            {
+                let start = (row as i128, col as i128);
+                let window: Option<Vec<Position>> = (0..steps)
+                    .map(|i| {
+                        wrap_position((start.0 + i * dir.0, start.1 + i * dir.1), size, topology)
+                    })
+                    .collect();
+                let window = match window
+                {
+                    Some(window) => window,
+                    None => continue,
+                };
+
+                // a line that wraps back onto itself doesn't have `length` different cells
+                if (1..window.len()).any(|i| window[..i].contains(&window[i]))
+                {
+                    continue;
+                }
+
+                let mut pieces = [0, 0];
+                for pos in window
+                {
+                    if let Cell::Piece(owner) = board[pos]
+                    {
+                        pieces[owner.id - 1] += 1;
+                    }
+                }
+                let (own, other) = match player.id
+                {
+                    1 => (pieces[0], pieces[1]),
+                    _ => (pieces[1], pieces[0]),
+                };
+                match (own, other)
+                {
+                    (n, 0) => score += (n * n) as f32,
+                    (0, n) => score -= (n * n) as f32,
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    score
+}
+
+/// How the edges of a board are joined together
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology
+{
+    /// Lines stop at every edge
+    #[default]
+    Flat,
+    /// The left and right edges are joined, so lines wrap around horizontally
+    Cylinder,
+    /// The left and right edges are joined, and so are the top and bottom
+    Torus,
+}
+
+impl Topology
+{
+    // whether (rows, cols) wrap around
+    fn wraps(&self) -> (bool, bool)
+    {
+        match self
+        {
+            Topology::Flat => (false, false),
+            Topology::Cylinder => (false, true),
+            Topology::Torus => (true, true),
+        }
+    }
+}
+
+/// `on_board` for boards whose edges can wrap around. Positions past an edge that wraps come back
+/// on the other side, and positions past any other edge are None.
+pub fn wrap_position(
+    pos: (i128, i128),
+    size: (usize, usize),
+    topology: Topology,
+) -> Option<Position>
+{
+    let (wrap_rows, wrap_cols) = topology.wraps();
+    let rows: i128 = size.0.try_into().unwrap();
+    let cols: i128 = size.1.try_into().unwrap();
+
+    let row = if wrap_rows
+    {
+        pos.0.rem_euclid(rows)
+    }
+    else
+    {
+        pos.0
+    };
+    let col = if wrap_cols
+    {
+        pos.1.rem_euclid(cols)
+    }
+    else
+    {
+        pos.1
+    };
+    match on_board((row, col), size)
+    {
+        true => Some(tuple_to_pos((row, col))),
+        false => None,
+    }
+}
+
+fn lcm(a: i128, b: i128) -> i128
+{
+    let (mut x, mut y) = (a, b);
+    while y != 0
+    {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
 pub fn on_board(pos: (i128, i128), size: (usize, usize)) -> bool
 {
     pos.0 >= 0
@@ -5,7 +5,7 @@ use std::{
 
 use crate::games::Player;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Board<T>
 {
     data: Vec<T>,
@@ -73,7 +73,7 @@ impl<T> IndexMut<Position> for Board<T>
     }
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum Cell
 {
     #[default]
@@ -0,0 +1,182 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+/// A board with layers, rows and columns, for games played in three dimensions
+#[derive(Clone, PartialEq, Eq)]
+pub struct Board3D<T>
+{
+    data: Vec<T>,
+    layers: usize,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Default> Board3D<T>
+{
+    pub fn new(layers: usize, rows: usize, cols: usize) -> Board3D<T>
+    {
+        Board3D {
+            data: (0..layers * rows * cols).map(|_| T::default()).collect(),
+            layers,
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T> Board3D<T>
+{
+    pub fn layers(&self) -> usize
+    {
+        self.layers
+    }
+
+    pub fn rows(&self) -> usize
+    {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize
+    {
+        self.cols
+    }
+
+    pub fn size(&self) -> (usize, usize, usize)
+    {
+        (self.layers, self.rows, self.cols)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position3D>
+    {
+        let (rows, cols) = (self.rows, self.cols);
+        (0..self.layers).flat_map(move |layer| {
+            (0..rows).flat_map(move |row| (0..cols).map(move |col| Position3D { layer, row, col }))
+        })
+    }
+}
+
+impl<T: Display> Display for Board3D<T>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        // layers are printed one after another, separated by a blank line
+        for layer in 0..self.layers
+        {
+            if layer > 0
+            {
+                writeln!(f)?;
+            }
+
+            for row in 0..self.rows
+            {
+                for col in 0..self.cols
+                {
+                    write!(f, "{} ", self.index(Position3D { layer, row, col }))?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Index<Position3D> for Board3D<T>
+{
+    type Output = T;
+
+    fn index(&self, index: Position3D) -> &Self::Output
+    {
+        &self.data[(index.layer * self.rows + index.row) * self.cols + index.col]
+    }
+}
+
+impl<T> IndexMut<Position3D> for Board3D<T>
+{
+    fn index_mut(&mut self, index: Position3D) -> &mut Self::Output
+    {
+        &mut self.data[(index.layer * self.rows + index.row) * self.cols + index.col]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Position3D
+{
+    pub layer: usize,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Display for Position3D
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "({}, {}, {})", self.layer, self.row, self.col)
+    }
+}
+
+/// One direction for each of the 13 kinds of line through a cell: 3 along the axes, 6 diagonals
+/// across a plane, and 4 diagonals through the cube. The opposite directions are the same lines.
+pub const DIRECTIONS: [(i128, i128, i128); 13] = [
+    (0, 0, 1),
+    (0, 1, 0),
+    (1, 0, 0),
+    (0, 1, 1),
+    (0, 1, -1),
+    (1, 0, 1),
+    (1, 0, -1),
+    (1, 1, 0),
+    (1, -1, 0),
+    (1, 1, 1),
+    (1, 1, -1),
+    (1, -1, 1),
+    (1, -1, -1),
+];
+
+/// The 3D version of `common::generate_line`, returning every position on the line through `pos`
+/// in order
+pub fn generate_line(
+    pos: Position3D,
+    dir: (i128, i128, i128),
+    size: (usize, usize, usize),
+) -> Vec<Position3D>
+{
+    let start_pos = (pos.layer as i128, pos.row as i128, pos.col as i128);
+
+    // walk back to one end of the line, then collect positions going forward
+    let mut pos = start_pos;
+    while on_board((pos.0 - dir.0, pos.1 - dir.1, pos.2 - dir.2), size)
+    {
+        pos = (pos.0 - dir.0, pos.1 - dir.1, pos.2 - dir.2);
+    }
+
+    let mut positions = vec![];
+    while on_board(pos, size)
+    {
+        positions.push(tuple_to_pos(pos));
+        pos = (pos.0 + dir.0, pos.1 + dir.1, pos.2 + dir.2);
+    }
+
+    positions
+}
+
+pub fn on_board(pos: (i128, i128, i128), size: (usize, usize, usize)) -> bool
+{
+    pos.0 >= 0
+        && pos.1 >= 0
+        && pos.2 >= 0
+        && pos.0 < size.0 as i128
+        && pos.1 < size.1 as i128
+        && pos.2 < size.2 as i128
+}
+
+pub fn tuple_to_pos(tuple: (i128, i128, i128)) -> Position3D
+{
+    Position3D {
+        layer: tuple.0.try_into().unwrap(),
+        row: tuple.1.try_into().unwrap(),
+        col: tuple.2.try_into().unwrap(),
+    }
+}
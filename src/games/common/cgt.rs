@@ -0,0 +1,405 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::ops::{Add, Neg};
+
+use crate::games::{GameState, Player};
+
+/// A game where the players can have different moves, described in combinatorial game theory
+/// terms. Player 1 is Left and player 2 is Right.
+pub trait PartizanGame: GameState
+{
+    type Key: Hash + Eq;
+
+    /// Identifies the position independently of the player to move and the move history
+    fn position_key(&self) -> Self::Key;
+
+    /// Returns every position `player` could move to, whether or not it is their turn
+    fn options(&self, player: Player) -> Vec<Self>;
+
+    /// Splits the position into independent parts whose values add up to the value of the whole.
+    /// A position that can't be split should return itself.
+    fn components(&self) -> Vec<Self>
+    {
+        vec![self.clone()]
+    }
+}
+
+/// Calculates the value of a position under normal play, where the player who can't move loses
+pub fn value<T>(state: &T) -> Value
+where
+    T: PartizanGame,
+{
+    value_cached(state, &mut HashMap::new())
+}
+
+fn value_cached<T>(state: &T, cache: &mut HashMap<T::Key, Value>) -> Value
+where
+    T: PartizanGame,
+{
+    let components = state.components();
+    if components.len() != 1
+    {
+        return components
+            .iter()
+            .map(|component| value_cached(component, cache))
+            .fold(Value::integer(0), |sum, value| sum + value);
+    }
+
+    let state = &components[0];
+    let key = state.position_key();
+    if let Some(value) = cache.get(&key)
+    {
+        return value.clone();
+    }
+
+    let mut options = [vec![], vec![]];
+    for id in [1, 2]
+    {
+        options[id - 1] = state
+            .options(Player { id })
+            .iter()
+            .map(|option| value_cached(option, cache))
+            .collect();
+    }
+    let [left, right] = options;
+    let value = Value::new(left, right);
+
+    cache.insert(key, value.clone());
+    value
+}
+
+/// Who wins a position with perfect play
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome
+{
+    /// Left (player 1) wins whoever moves first
+    Left,
+    /// Right (player 2) wins whoever moves first
+    Right,
+    /// The player who moves second wins
+    Previous,
+    /// The player who moves first wins
+    Next,
+}
+
+/// The value of a position in canonical form, as the values Left and Right can move to.
+///
+/// Values are compared as games, so `a <= b` means `b` is at least as good for Left as `a`. Some
+/// values, like `*`, are neither greater than, less than or equal to 0, and `partial_cmp`
+/// returns `None` for those.
+#[derive(Clone, Debug)]
+pub struct Value
+{
+    left: Vec<Value>,
+    right: Vec<Value>,
+}
+
+impl Value
+{
+    /// Builds the value `{ left | right }`, simplified to canonical form
+    pub fn new(left: Vec<Value>, right: Vec<Value>) -> Value
+    {
+        let mut value = Value { left, right };
+        loop
+        {
+            let bypassed = value.bypass_reversible();
+            let removed = value.remove_dominated();
+            if !bypassed && !removed
+            {
+                return value;
+            }
+        }
+    }
+
+    pub fn integer(n: i64) -> Value
+    {
+        match n.cmp(&0)
+        {
+            Ordering::Equal => Value::new(vec![], vec![]),
+            Ordering::Greater => Value::new(vec![Value::integer(n - 1)], vec![]),
+            Ordering::Less => Value::new(vec![], vec![Value::integer(n + 1)]),
+        }
+    }
+
+    /// `{ 0 | 0 }`, where whoever moves next wins
+    pub fn star() -> Value
+    {
+        Value::new(vec![Value::integer(0)], vec![Value::integer(0)])
+    }
+
+    pub fn outcome(&self) -> Outcome
+    {
+        let zero = Value::integer(0);
+        match (zero.at_most(self), self.at_most(&zero))
+        {
+            (true, true) => Outcome::Previous,
+            (true, false) => Outcome::Left,
+            (false, true) => Outcome::Right,
+            (false, false) => Outcome::Next,
+        }
+    }
+
+    /// Returns the value as a number, if it is one. Numbers in canonical form have denominators
+    /// that are powers of 2, so they can be represented exactly.
+    pub fn number(&self) -> Option<f64>
+    {
+        let left: Option<Vec<f64>> = self.left.iter().map(Value::number).collect();
+        let right: Option<Vec<f64>> = self.right.iter().map(Value::number).collect();
+        let low = left?.into_iter().reduce(f64::max);
+        let high = right?.into_iter().reduce(f64::min);
+        if let (Some(low), Some(high)) = (low, high)
+        {
+            if low >= high
+            {
+                return None;
+            }
+        }
+
+        // adding 0 turns -0 into 0
+        Some(simplest_between(low, high) + 0.0)
+    }
+
+    // self <= other as games, when no Left option of self is at least other and no Right option of other
+    // is at most self
+    fn at_most(&self, other: &Value) -> bool
+    {
+        !self.left.iter().any(|left| other.at_most(left))
+            && !other.right.iter().any(|right| right.at_most(self))
+    }
+
+    // a Left option is reversible when Right can answer it with something at most as good for
+    // Left as the original value, in which case it is replaced by the Left options after that
+    // answer. Right options are the same the other way around.
+    fn bypass_reversible(&mut self) -> bool
+    {
+        let original = self.clone();
+        let mut changed = false;
+
+        let mut left = vec![];
+        for option in std::mem::take(&mut self.left)
+        {
+            match option.right.iter().find(|answer| answer.at_most(&original))
+            {
+                Some(answer) =>
+                {
+                    left.extend(answer.left.iter().cloned());
+                    changed = true;
+                },
+                None => left.push(option),
+            }
+        }
+        self.left = left;
+
+        let mut right = vec![];
+        for option in std::mem::take(&mut self.right)
+        {
+            match option.left.iter().find(|answer| original.at_most(answer))
+            {
+                Some(answer) =>
+                {
+                    right.extend(answer.right.iter().cloned());
+                    changed = true;
+                },
+                None => right.push(option),
+            }
+        }
+        self.right = right;
+
+        changed
+    }
+
+    // keeps only the best options for each player, and one of any equal options
+    fn remove_dominated(&mut self) -> bool
+    {
+        let (left_count, right_count) = (self.left.len(), self.right.len());
+        self.left = keep_best(std::mem::take(&mut self.left), |a, b| a.at_most(b));
+        self.right = keep_best(std::mem::take(&mut self.right), |a, b| b.at_most(a));
+        self.left.len() != left_count || self.right.len() != right_count
+    }
+}
+
+// keeps the options that aren't `worse` than another option, keeping the first of equal ones
+fn keep_best(options: Vec<Value>, worse: impl Fn(&Value, &Value) -> bool) -> Vec<Value>
+{
+    let mut kept: Vec<Value> = vec![];
+    for (i, option) in options.iter().enumerate()
+    {
+        let dominated = options
+            .iter()
+            .enumerate()
+            .any(|(j, other)| j != i && worse(option, other) && (!worse(other, option) || j < i));
+        if !dominated
+        {
+            kept.push(option.clone());
+        }
+    }
+    kept
+}
+
+// the number with the smallest denominator (and then closest to 0) strictly between `low` and
+// `high`, where a missing bound means there is no limit on that side
+fn simplest_between(low: Option<f64>, high: Option<f64>) -> f64
+{
+    match (low, high)
+    {
+        (low, high) if low.is_none_or(|l| l < 0.0) && high.is_none_or(|h| h > 0.0) => 0.0,
+        (Some(low), None) => low.floor() + 1.0,
+        (None, Some(high)) => high.ceil() - 1.0,
+        (Some(low), Some(high)) if high <= 0.0 => -simplest_between(Some(-high), Some(-low)),
+        (Some(low), Some(high)) =>
+        {
+            let mut scale = 1.0;
+            loop
+            {
+                let candidate = ((low * scale).floor() + 1.0) / scale;
+                if candidate < high
+                {
+                    return candidate;
+                }
+                scale *= 2.0;
+            }
+        },
+        (None, None) => unreachable!(),
+    }
+}
+
+impl PartialEq for Value
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.at_most(other) && other.at_most(self)
+    }
+}
+
+impl PartialOrd for Value
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+    {
+        match (self.at_most(other), other.at_most(self))
+        {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl Add for Value
+{
+    type Output = Value;
+
+    /// The value of playing both games side by side, where each move is made in one of them
+    fn add(self, other: Value) -> Value
+    {
+        let mut left = vec![];
+        let mut right = vec![];
+        for option in &self.left
+        {
+            left.push(option.clone() + other.clone());
+        }
+        for option in &other.left
+        {
+            left.push(self.clone() + option.clone());
+        }
+        for option in &self.right
+        {
+            right.push(option.clone() + other.clone());
+        }
+        for option in &other.right
+        {
+            right.push(self.clone() + option.clone());
+        }
+
+        Value::new(left, right)
+    }
+}
+
+impl Neg for Value
+{
+    type Output = Value;
+
+    fn neg(self) -> Value
+    {
+        Value::new(
+            self.right.into_iter().map(Value::neg).collect(),
+            self.left.into_iter().map(Value::neg).collect(),
+        )
+    }
+}
+
+impl Display for Value
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        if let Some(number) = self.number()
+        {
+            // write fractions like 3/4 rather than 0.75
+            let mut denominator = 1;
+            while number * denominator as f64 != (number * denominator as f64).floor()
+            {
+                denominator *= 2;
+            }
+            return match denominator
+            {
+                1 => write!(f, "{number}"),
+                _ => write!(f, "{}/{}", number * denominator as f64, denominator),
+            };
+        }
+        if *self == Value::star()
+        {
+            return write!(f, "*");
+        }
+
+        let join = |options: &Vec<Value>| {
+            options
+                .iter()
+                .map(|option| option.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        write!(f, "{{{} | {}}}", join(&self.left), join(&self.right))
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::{Outcome, Value};
+
+    #[test]
+    fn test_numbers()
+    {
+        let zero = Value::integer(0);
+        let one = Value::integer(1);
+        let half = Value::new(vec![zero.clone()], vec![one.clone()]);
+
+        assert_eq!(half.number(), Some(0.5));
+        assert!(half.clone() + half.clone() == one);
+        assert!(one.clone() + -one.clone() == zero);
+        assert!(zero < half && half < one);
+        assert_eq!((-half.clone() + -one.clone()).to_string(), "-3/2");
+
+        // dominated and reversible options simplify away
+        let two = Value::integer(2);
+        assert!(Value::new(vec![zero.clone(), one.clone()], vec![]) == two);
+        assert_eq!(Value::new(vec![-one.clone()], vec![two]).to_string(), "0");
+    }
+
+    #[test]
+    fn test_outcomes()
+    {
+        let star = Value::star();
+        assert_eq!(star.outcome(), Outcome::Next);
+        assert_eq!((star.clone() + star.clone()).outcome(), Outcome::Previous);
+        assert!(star.partial_cmp(&Value::integer(0)).is_none());
+        assert_eq!(star.to_string(), "*");
+
+        let switch = Value::new(vec![Value::integer(1)], vec![Value::integer(-1)]);
+        assert_eq!(switch.outcome(), Outcome::Next);
+        assert_eq!(switch.to_string(), "{1 | -1}");
+        assert_eq!((switch + Value::integer(2)).outcome(), Outcome::Left);
+    }
+}
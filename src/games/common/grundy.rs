@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::games::{GameResult, GameState};
+
+/// A game where both players always have the same moves available, so a position can be
+/// described without saying whose turn it is.
+pub trait ImpartialGame: GameState
+{
+    type Key: Hash + Eq;
+
+    /// Identifies the position independently of the player to move and the move history
+    fn position_key(&self) -> Self::Key;
+}
+
+/// Calculates the Grundy value (nimber) of a position under normal play, where the player who
+/// can't move loses. A position is lost for the player to move exactly when its value is 0.
+pub fn grundy_value<T>(state: &T) -> usize
+where
+    T: ImpartialGame,
+{
+    grundy_value_cached(state, &mut HashMap::new())
+}
+
+fn grundy_value_cached<T>(state: &T, cache: &mut HashMap<T::Key, usize>) -> usize
+where
+    T: ImpartialGame,
+{
+    let key = state.position_key();
+    if let Some(&value) = cache.get(&key)
+    {
+        return value;
+    }
+
+    let values: HashSet<usize> = state
+        .get_valid_moves()
+        .into_iter()
+        .map(|m| grundy_value_cached(&state.clone().do_move(m), cache))
+        .collect();
+    let value = mex(&values);
+
+    cache.insert(key, value);
+    value
+}
+
+/// Returns the smallest non-negative integer that isn't in `values`
+pub fn mex(values: &HashSet<usize>) -> usize
+{
+    (0..).find(|v| !values.contains(v)).unwrap()
+}
+
+/// Solves whether the player to move can force a win. Unlike `grundy_value` this uses the result
+/// from `check_win`, so it also works for misère play. Assumes the players alternate turns.
+pub fn is_winning<T>(state: &T) -> bool
+where
+    T: ImpartialGame,
+{
+    is_winning_cached(state, &mut HashMap::new())
+}
+
+fn is_winning_cached<T>(state: &T, cache: &mut HashMap<T::Key, bool>) -> bool
+where
+    T: ImpartialGame,
+{
+    match state.check_win()
+    {
+        GameResult::InProgress =>
+        {},
+        GameResult::Win(player) => return player == state.player_to_move(),
+        GameResult::Draw => return false,
+        GameResult::Score(_) => panic!("impartial games have two players"),
+    }
+
+    let key = state.position_key();
+    if let Some(&winning) = cache.get(&key)
+    {
+        return winning;
+    }
+
+    // winning if there is a move that leaves the opponent in a losing position
+    let winning = state
+        .get_valid_moves()
+        .into_iter()
+        .any(|m| !is_winning_cached(&state.clone().do_move(m), cache));
+
+    cache.insert(key, winning);
+    winning
+}
@@ -1,12 +1,20 @@
 use core::panic;
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
 use crate::games::{
     common::board::{Board, Cell, Position},
     GameResult, GameState, Player,
 };
 
-use super::common::generate_line;
+use super::common::{evaluate_windows, generate_wrapped_line, Topology};
+use crate::players::minimax::Evaluator;
+
+/// Number of pieces a player has to set aside to win Pop 10
+const POP_10_TARGET: usize = 10;
 
 #[derive(Clone)]
 pub struct Connect4
@@ -14,6 +22,14 @@ pub struct Connect4
     board: Board<Cell>,
     num_to_win: usize,
     open_positions: Vec<Position>,
+    variant: Connect4Variant,
+    topology: Topology,
+    to_move: Player,
+    // pieces each player has set aside in Pop 10
+    set_aside: [usize; 2],
+    // a hash of every position so far, to detect repetition in variants where pieces can be
+    // removed
+    history: Vec<u64>,
     last_move: Option<Connect4Move>,
 }
 
@@ -22,79 +38,142 @@ impl Connect4
     pub fn new(rows: usize, cols: usize, num_to_win: usize) -> Connect4
     {
         let open_positions = (0..cols)
-            .map(|i| Position { row: rows - 1, col: i })
+            .map(|i| Position {
+                row: rows - 1,
+                col: i,
+            })
             .collect();
 
         Connect4 {
             board: Board::new(rows, cols),
             num_to_win,
             open_positions,
+            variant: Connect4Variant::Standard,
+            topology: Topology::Flat,
+            to_move: Player { id: 1 },
+            set_aside: [0, 0],
+            history: vec![],
             last_move: None,
         }
     }
-}
 
-impl GameState for Connect4
-{
-    type Move = Connect4Move;
+    pub fn set_variant(mut self, variant: Connect4Variant) -> Connect4
+    {
+        self.variant = variant;
+        self.history = match variant
+        {
+            // pieces are only ever added, so positions can't repeat
+            Connect4Variant::Standard => vec![],
+            _ => vec![self.position_hash()],
+        };
+        self
+    }
 
-    fn get_valid_moves(&self) -> Vec<Self::Move>
+    /// Joining the left and right edges with `Topology::Cylinder` lets rows and diagonals wrap
+    /// around. Gravity only works in one direction, so the board can't be a torus.
+    pub fn set_topology(mut self, topology: Topology) -> Connect4
     {
-        // if game is over no moves allowed
-        if self.check_win() != GameResult::InProgress
+        if topology == Topology::Torus
         {
-            return vec![];
+            panic!("connect 4 can't be played on a torus");
         }
-        self.open_positions
-            .iter()
-            .map(|p| Connect4Move {
-                position: *p,
-                player: self.player_to_move(),
-            })
-            .collect()
+
+        self.topology = topology;
+        self
     }
 
-    fn player_to_move(&self) -> Player
+    /// Returns the number of pieces `player` has set aside in Pop 10
+    pub fn set_aside(&self, player: Player) -> usize
+    {
+        self.set_aside[player.id - 1]
+    }
+
+    fn bottom(&self, col: usize) -> Position
+    {
+        Position {
+            row: self.board.rows() - 1,
+            col,
+        }
+    }
+
+    fn is_setup(&self) -> bool
+    {
+        // the board gets filled before any piece can be set aside
+        self.set_aside == [0, 0] && !self.open_positions.is_empty()
+    }
+
+    fn completes_line(&self, pos: Position, player: Player) -> bool
     {
-        match self.last_move
+        let board = &self.board;
+
+        for dir in [(-1, -1), (-1, 0), (-1, 1), (0, 1)]
         {
-            Some(last_move) =>
+            // get a list of all the cells on the row, column, or diagonal going through pos
+            let line = generate_wrapped_line(
+                pos,
+                dir,
+                (board.rows(), board.cols()),
+                self.topology,
+                self.num_to_win,
+            );
+            let mut consecutive = 0;
+            for pos in line
             {
-                if last_move.player.id == 1
+                if board[pos] == Cell::Piece(player)
                 {
-                    Player { id: 2 }
+                    consecutive += 1;
+
+                    if consecutive >= self.num_to_win
+                    {
+                        return true;
+                    }
                 }
                 else
                 {
-                    Player { id: 1 }
+                    consecutive = 0;
                 }
-            },
-            None => Player { id: 1 },
+            }
         }
+
+        false
     }
 
-    fn do_move(mut self, m: Self::Move) -> Self
+    // popping shifts a whole column, so lines can appear anywhere on the board
+    fn has_line(&self, player: Player) -> bool
     {
-        // change board data based on move
-        self.board[m.position] = Cell::Piece(m.player);
+        (0..self.board.rows())
+            .flat_map(|row| (0..self.board.cols()).map(move |col| Position { row, col }))
+            .any(|pos| self.board[pos] == Cell::Piece(player) && self.completes_line(pos, player))
+    }
 
-        // update the last_move so that all other logic works
-        self.last_move = Some(m);
+    // the board and the player to move, which is all that matters for repetition
+    fn position_hash(&self) -> u64
+    {
+        let mut hasher = DefaultHasher::new();
+        (&self.board, self.to_move).hash(&mut hasher);
+        hasher.finish()
+    }
 
+    fn is_repeated(&self) -> bool
+    {
+        let current = self.position_hash();
+        self.history.iter().filter(|&&hash| hash == current).count() >= 3
+    }
+
+    fn drop_piece(&mut self, position: Position, player: Player)
+    {
         // find the index of the position played so we can modify / delete it
         let index = self
             .open_positions
             .iter()
             // there is only one position open in each column so only need to check that the column matches
-            .position(|pos| pos.col == m.position.col);
+            .position(|pos| pos.col == position.col)
+            .expect("column should be open");
 
-        let index = match index
-        {
-            Some(i) => i,
-            None => panic!("the move: {m} was not a valid move"),
-        };
+        // change board data based on move
+        self.board[position] = Cell::Piece(player);
 
-        if m.position.row == 0
+        if position.row == 0
         {
             // if we reach the top of the column, there are no more valid
             // positions open, so remove the position from the vec
@@ -108,6 +187,238 @@ impl GameState for Connect4
             // position to be (row - 1, col)
             self.open_positions[index].row -= 1;
         }
+    }
+
+    fn pop_piece(&mut self, col: usize)
+    {
+        // shift everything above the bottom piece down by one
+        for row in (1..self.board.rows()).rev()
+        {
+            self.board[Position { row, col }] = self.board[Position { row: row - 1, col }];
+        }
+        self.board[Position { row: 0, col }] = Cell::Empty;
+
+        match self.open_positions.iter_mut().find(|pos| pos.col == col)
+        {
+            Some(pos) => pos.row += 1,
+            None => self.open_positions.push(Position { row: 0, col }),
+        }
+    }
+
+    fn drops(&self, positions: &[Position]) -> Vec<Connect4Move>
+    {
+        positions
+            .iter()
+            .map(|&position| Connect4Move {
+                kind: Connect4MoveKind::Drop,
+                position,
+                player: self.to_move,
+            })
+            .collect()
+    }
+
+    // in Pop 10, a popped piece that isn't part of a line goes back in at the top of a different
+    // column if there is room for it, and otherwise back into its own column
+    fn returns_after_pop(&self, col: usize) -> Vec<Position>
+    {
+        let others: Vec<Position> = self
+            .open_positions
+            .iter()
+            .copied()
+            .filter(|pos| pos.col != col)
+            .collect();
+        if !others.is_empty()
+        {
+            return others;
+        }
+
+        // popping shifts the column down, which leaves room at the top
+        match self.open_positions.iter().find(|pos| pos.col == col)
+        {
+            Some(pos) => vec![Position {
+                row: pos.row + 1,
+                col,
+            }],
+            None => vec![Position { row: 0, col }],
+        }
+    }
+
+    // whether `m` follows the rules of the variant, checking only that one move
+    fn is_valid(&self, m: Connect4Move) -> bool
+    {
+        let position = m.position;
+        if m.player != self.to_move
+            || position.row >= self.board.rows()
+            || position.col >= self.board.cols()
+        {
+            return false;
+        }
+
+        let pops_own =
+            position == self.bottom(position.col) && self.board[position] == Cell::Piece(m.player);
+        match (m.kind, self.variant)
+        {
+            (Connect4MoveKind::Drop, Connect4Variant::Pop10) =>
+            {
+                // each row has to be filled before starting on the one above it
+                let lowest = self.open_positions.iter().map(|pos| pos.row).max();
+                self.is_setup()
+                    && self.open_positions.contains(&position)
+                    && Some(position.row) == lowest
+            },
+            (Connect4MoveKind::Drop, _) => self.open_positions.contains(&position),
+            (Connect4MoveKind::Pop, Connect4Variant::PopOut) => pops_own,
+            (Connect4MoveKind::Pop, Connect4Variant::Pop10) =>
+            {
+                !self.is_setup() && pops_own && self.completes_line(position, m.player)
+            },
+            (Connect4MoveKind::PopAndReturn(to), Connect4Variant::Pop10) =>
+            {
+                !self.is_setup()
+                    && pops_own
+                    && !self.completes_line(position, m.player)
+                    && self.returns_after_pop(position.col).contains(&to)
+            },
+            _ => false,
+        }
+    }
+
+    // every move that follows the rules of the variant, without checking if the game is over
+    fn moves(&self) -> Vec<Connect4Move>
+    {
+        let player = self.to_move;
+
+        match self.variant
+        {
+            Connect4Variant::Standard => self.drops(&self.open_positions),
+            Connect4Variant::PopOut =>
+            {
+                let mut moves = self.drops(&self.open_positions);
+                for col in 0..self.board.cols()
+                {
+                    let position = self.bottom(col);
+                    if self.board[position] == Cell::Piece(player)
+                    {
+                        moves.push(Connect4Move {
+                            kind: Connect4MoveKind::Pop,
+                            position,
+                            player,
+                        });
+                    }
+                }
+                moves
+            },
+            Connect4Variant::Pop10 if self.is_setup() =>
+            {
+                // each row has to be filled before starting on the one above it
+                let lowest = self.open_positions.iter().map(|pos| pos.row).max();
+                let open: Vec<Position> = self
+                    .open_positions
+                    .iter()
+                    .copied()
+                    .filter(|pos| Some(pos.row) == lowest)
+                    .collect();
+                self.drops(&open)
+            },
+            Connect4Variant::Pop10 =>
+            {
+                let mut moves = vec![];
+                for col in 0..self.board.cols()
+                {
+                    let position = self.bottom(col);
+                    if self.board[position] != Cell::Piece(player)
+                    {
+                        continue;
+                    }
+
+                    // pieces that are part of a line get set aside
+                    if self.completes_line(position, player)
+                    {
+                        moves.push(Connect4Move {
+                            kind: Connect4MoveKind::Pop,
+                            position,
+                            player,
+                        });
+                        continue;
+                    }
+
+                    for to in self.returns_after_pop(col)
+                    {
+                        moves.push(Connect4Move {
+                            kind: Connect4MoveKind::PopAndReturn(to),
+                            position,
+                            player,
+                        });
+                    }
+                }
+                moves
+            },
+        }
+    }
+}
+
+impl GameState for Connect4
+{
+    type Move = Connect4Move;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        // if game is over no moves allowed
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+        self.moves()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if !self.is_valid(m)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        let mut extra_turn = false;
+        match m.kind
+        {
+            Connect4MoveKind::Drop => self.drop_piece(m.position, m.player),
+            Connect4MoveKind::Pop =>
+            {
+                self.pop_piece(m.position.col);
+                if self.variant == Connect4Variant::Pop10
+                {
+                    // setting a piece aside gives the player another turn
+                    self.set_aside[m.player.id - 1] += 1;
+                    extra_turn = true;
+                }
+            },
+            Connect4MoveKind::PopAndReturn(to) =>
+            {
+                self.pop_piece(m.position.col);
+                self.drop_piece(to, m.player);
+            },
+        }
+
+        // update the last_move so that all other logic works
+        self.last_move = Some(m);
+        if !extra_turn
+        {
+            self.to_move = match m.player.id
+            {
+                1 => Player { id: 2 },
+                _ => Player { id: 1 },
+            };
+        }
+
+        if self.variant != Connect4Variant::Standard
+        {
+            self.history.push(self.position_hash());
+        }
 
         self
     }
@@ -120,35 +431,50 @@ impl GameState for Connect4
             None => return GameResult::InProgress,
         };
 
-        let board = &self.board;
-
-        let player = last_move.player;
-        let start_pos = last_move.position;
-
-        for dir in [(-1, -1), (-1, 0), (-1, 1), (0, 1)]
+        match self.variant
         {
-            // get a list of all the cells on the row, column, or diagonal going through start_pos
-            let line = generate_line(start_pos, dir, (board.rows(), board.cols()));
-            let mut consecutive = 0;
-            for pos in line
+            Connect4Variant::Standard =>
             {
-                if board[pos] == Cell::Piece(player)
+                if self.completes_line(last_move.position, last_move.player)
                 {
-                    consecutive += 1;
+                    return GameResult::Win(last_move.player);
+                }
 
-                    if consecutive >= self.num_to_win
-                    {
-                        return GameResult::Win(player);
-                    }
+                if self.open_positions.is_empty()
+                {
+                    return GameResult::Draw;
                 }
-                else
+
+                return GameResult::InProgress;
+            },
+            Connect4Variant::PopOut =>
+            {
+                let p1 = self.has_line(Player { id: 1 });
+                let p2 = self.has_line(Player { id: 2 });
+                match (p1, p2)
                 {
-                    consecutive = 0;
+                    // a pop that gives both players a line wins for the player who popped
+                    (true, true) => return GameResult::Win(last_move.player),
+                    (true, false) => return GameResult::Win(Player { id: 1 }),
+                    (false, true) => return GameResult::Win(Player { id: 2 }),
+                    (false, false) => (),
                 }
-            }
+            },
+            Connect4Variant::Pop10 =>
+            {
+                for id in [1, 2]
+                {
+                    if self.set_aside[id - 1] >= POP_10_TARGET
+                    {
+                        return GameResult::Win(Player { id });
+                    }
+                }
+            },
         }
 
-        if self.open_positions.is_empty()
+        // the same position three times, or a player with nothing they can pop or drop, is a
+        // draw
+        if self.is_repeated() || self.moves().is_empty()
         {
             return GameResult::Draw;
         }
@@ -168,6 +494,10 @@ impl Display for Connect4
     {
         writeln!(f, "Board: ")?;
         write!(f, "{}", self.board)?;
+        if self.variant == Connect4Variant::Pop10
+        {
+            writeln!(f, "Set Aside: {}, {}", self.set_aside[0], self.set_aside[1])?;
+        }
         if self.check_win() == GameResult::InProgress
         {
             writeln!(f, "Next Player: {}", self.player_to_move())?;
@@ -179,9 +509,39 @@ impl Display for Connect4
         Ok(())
     }
 }
-#[derive(Clone, Copy)]
+
+/// The rules being played. In both pop variants, repeating a position three times is a draw.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Connect4Variant
+{
+    /// Pieces are dropped into the top of a column, and the first line wins
+    Standard,
+    /// A player can also remove one of their own pieces from the bottom of a column instead of
+    /// dropping one, and everything above it shifts down. A full board isn't a draw while there
+    /// are still pieces to pop.
+    PopOut,
+    /// Players fill the board one row at a time, then take turns popping their own pieces from
+    /// the bottom row. A piece that is part of a line is set aside and the player moves again,
+    /// and any other piece goes back in at the top of another column. The first player to set
+    /// aside 10 pieces wins.
+    Pop10,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Connect4MoveKind
+{
+    Drop,
+    // remove the piece at the bottom of the column, which is set aside in Pop 10
+    Pop,
+    // Pop 10 only, put the popped piece back in at this position
+    PopAndReturn(Position),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Connect4Move
 {
+    kind: Connect4MoveKind,
+    // where the piece is dropped, or the bottom of the column for pops
     position: Position,
     player: Player,
 }
@@ -190,6 +550,265 @@ impl Display for Connect4Move
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
     {
-        write!(f, "{}, Position: {}", self.player, self.position)
+        match self.kind
+        {
+            Connect4MoveKind::Drop => write!(f, "{}, Position: {}", self.player, self.position),
+            Connect4MoveKind::Pop => write!(f, "{}, Pop: {}", self.player, self.position),
+            Connect4MoveKind::PopAndReturn(to) =>
+            {
+                write!(f, "{}, Pop: {}, Return: {}", self.player, self.position, to)
+            },
+        }
+    }
+}
+
+/// Counts the lines each player could still complete, for searches that stop before the end of
+/// the game
+#[derive(Clone, Copy, Default)]
+pub struct Connect4Evaluator;
+
+impl Evaluator<Connect4> for Connect4Evaluator
+{
+    fn evaluate(&self, state: &Connect4) -> f32
+    {
+        evaluate_windows(
+            &state.board,
+            state.topology,
+            state.num_to_win,
+            state.player_to_move(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{
+        common::{
+            board::{Cell, Position},
+            Topology,
+        },
+        GameResult, GameState, Player,
+    };
+    use crate::players::{random::RandomPlayer, GamePlayer};
+
+    use super::{Connect4, Connect4Move, Connect4MoveKind, Connect4Variant};
+
+    fn find(game: &Connect4, kind: Connect4MoveKind, row: usize, col: usize) -> Connect4Move
+    {
+        *game
+            .get_valid_moves()
+            .iter()
+            .find(|m| m.kind == kind && m.position == Position { row, col })
+            .expect("move should be valid")
+    }
+
+    fn drop_in(game: Connect4, col: usize) -> Connect4Move
+    {
+        *game
+            .get_valid_moves()
+            .iter()
+            .find(|m| m.kind == Connect4MoveKind::Drop && m.position.col == col)
+            .expect("column should be open")
+    }
+
+    fn play_columns(mut game: Connect4, cols: &[usize]) -> Connect4
+    {
+        for &col in cols
+        {
+            let m = drop_in(game.clone(), col);
+            game = game.do_move(m);
+        }
+        game
+    }
+
+    #[test]
+    fn test_standard()
+    {
+        let game = play_columns(Connect4::new(6, 7, 4), &[0, 1, 0, 1, 0, 1]);
+        assert!(game.check_win() == GameResult::InProgress);
+        assert!(game
+            .get_valid_moves()
+            .iter()
+            .all(|m| m.kind == Connect4MoveKind::Drop));
+
+        let game = play_columns(game, &[0]);
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    fn test_cylinder()
+    {
+        // columns 5, 6, 0 and 1 are next to each other when the board wraps around
+        let cols = [5, 5, 6, 6, 0, 0, 1];
+        for (topology, result) in [
+            (Topology::Flat, GameResult::InProgress),
+            (Topology::Cylinder, GameResult::Win(Player::new(1))),
+        ]
+        {
+            let game = play_columns(Connect4::new(6, 7, 4).set_topology(topology), &cols);
+            assert!(game.check_win() == result);
+        }
+    }
+
+    #[test]
+    fn test_popout_shifts_column()
+    {
+        let game = Connect4::new(6, 7, 4).set_variant(Connect4Variant::PopOut);
+        let game = play_columns(game, &[3, 3, 3]);
+
+        // player 2 can only pop from columns where their own piece is at the bottom
+        assert!(!game
+            .get_valid_moves()
+            .iter()
+            .any(|m| m.kind == Connect4MoveKind::Pop));
+
+        let game = play_columns(game, &[4]);
+        let m = find(&game, Connect4MoveKind::Pop, 5, 3);
+        let game = game.do_move(m);
+        assert!(game.board[Position { row: 5, col: 3 }] == Cell::Piece(Player::new(2)));
+        assert!(game.board[Position { row: 4, col: 3 }] == Cell::Piece(Player::new(1)));
+        assert!(game.board[Position { row: 3, col: 3 }] == Cell::Empty);
+        assert!(game.open_positions.contains(&Position { row: 3, col: 3 }));
+    }
+
+    #[test]
+    fn test_popout_simultaneous_lines()
+    {
+        // column 0 from the bottom is 1, 2, 1, with three of player 2's pieces next to it along
+        // the bottom row and three of player 1's pieces in the row above
+        let game = Connect4::new(6, 7, 4).set_variant(Connect4Variant::PopOut);
+        let game = play_columns(game, &[0, 0, 0, 1, 1, 2, 2, 3, 3, 6]);
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // popping column 0 completes both rows, and the player who popped wins
+        let m = find(&game, Connect4MoveKind::Pop, 5, 0);
+        let game = game.do_move(m);
+        assert!(game.has_line(Player::new(1)) && game.has_line(Player::new(2)));
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    fn test_popout_repetition()
+    {
+        fn pop_both(mut game: Connect4) -> Connect4
+        {
+            for (row, col) in [(5, 0), (5, 1)]
+            {
+                let m = find(&game, Connect4MoveKind::Pop, row, col);
+                game = game.do_move(m);
+            }
+            game
+        }
+
+        // both players popping their only piece goes back to the empty board
+        let game = Connect4::new(6, 7, 4).set_variant(Connect4Variant::PopOut);
+        let game = pop_both(play_columns(game, &[0, 1]));
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // which is the third time it has been seen
+        let game = pop_both(play_columns(game, &[0, 1]));
+        assert!(game.check_win() == GameResult::Draw);
+    }
+
+    #[test]
+    fn test_pop10()
+    {
+        let mut game = Connect4::new(6, 7, 4).set_variant(Connect4Variant::Pop10);
+
+        // during setup only the lowest row that isn't full can be played
+        assert_eq!(game.get_valid_moves().len(), 7);
+        game = play_columns(game, &[0]);
+        assert!(game.get_valid_moves().iter().all(|m| m.position.row == 5));
+
+        // player 1 gets a line along the bottom row, and the rest of the board is filled so
+        // that none of the other bottom pieces are part of a line
+        game = play_columns(game, &[4, 1, 5, 2, 6, 3]);
+        for row in (0..5).rev()
+        {
+            let cols: Vec<usize> = match row
+            {
+                2 => vec![1, 2, 3, 4, 5, 6, 0],
+                _ => (0..7).collect(),
+            };
+            game = play_columns(game, &cols);
+        }
+        assert!(game.open_positions.is_empty());
+        assert!(game.player_to_move() == Player::new(1));
+
+        // setting aside a piece from the line gives player 1 another turn
+        assert!(game
+            .get_valid_moves()
+            .iter()
+            .all(|m| m.kind == Connect4MoveKind::Pop));
+        let m = find(&game, Connect4MoveKind::Pop, 5, 0);
+        let game = game.do_move(m);
+        assert_eq!(game.set_aside(Player::new(1)), 1);
+        assert!(game.player_to_move() == Player::new(1));
+
+        // the line is broken now, so the next piece has to go back in, and column 0 is the only
+        // other column with room
+        let moves = game.get_valid_moves();
+        assert_eq!(moves.len(), 3);
+        let top_left = Position { row: 0, col: 0 };
+        assert!(moves
+            .iter()
+            .all(|m| m.kind == Connect4MoveKind::PopAndReturn(top_left)));
+
+        let game = game.do_move(moves[0]);
+        assert!(game.player_to_move() == Player::new(2));
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_single_move_check()
+    {
+        // checking one move has to agree with generating all of them, for every kind of move
+        // either player could try
+        for variant in [
+            Connect4Variant::Standard,
+            Connect4Variant::PopOut,
+            Connect4Variant::Pop10,
+        ]
+        {
+            let mut game = Connect4::new(4, 5, 3).set_variant(variant);
+            let mut player = RandomPlayer::from_seed(0);
+            for _ in 0..60
+            {
+                if game.check_win() != GameResult::InProgress
+                {
+                    break;
+                }
+
+                let valid = game.moves();
+                let positions: Vec<Position> = (0..4)
+                    .flat_map(|row| (0..5).map(move |col| Position { row, col }))
+                    .collect();
+                let mut kinds = vec![Connect4MoveKind::Drop, Connect4MoveKind::Pop];
+                kinds.extend(
+                    positions
+                        .iter()
+                        .map(|&to| Connect4MoveKind::PopAndReturn(to)),
+                );
+                for &position in &positions
+                {
+                    for &kind in &kinds
+                    {
+                        for id in [1, 2]
+                        {
+                            let m = Connect4Move {
+                                kind,
+                                position,
+                                player: Player::new(id),
+                            };
+                            assert_eq!(game.is_valid(m), valid.contains(&m), "{m}");
+                        }
+                    }
+                }
+
+                let m = player.choose_move(&game);
+                game = game.do_move(m);
+            }
+        }
     }
 }
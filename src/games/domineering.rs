@@ -0,0 +1,416 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Position},
+        cgt::PartizanGame,
+    },
+    GameResult, GameState, Player,
+};
+
+/// Domineering, where players take turns placing dominoes on a grid. Player 1 places them
+/// vertically and player 2 horizontally, and the last player to place one wins.
+///
+/// Covered squares split the board into regions that can be played independently, which is what
+/// `components` uses to calculate values with `common::cgt`.
+#[derive(Clone)]
+pub struct Domineering
+{
+    board: Board<DomineeringCell>,
+    to_move: Player,
+    last_move: Option<DomineeringMove>,
+}
+
+impl Domineering
+{
+    pub fn new(rows: usize, cols: usize) -> Domineering
+    {
+        Domineering {
+            board: Board::new(rows, cols),
+            to_move: Player { id: 1 },
+            last_move: None,
+        }
+    }
+
+    pub fn set_first_player(mut self, player: Player) -> Domineering
+    {
+        self.to_move = player;
+        self
+    }
+
+    /// Blocks a square so no domino can cover it, for playing on boards that aren't rectangles
+    pub fn set_blocked(mut self, position: Position) -> Domineering
+    {
+        self.board[position] = DomineeringCell::Blocked;
+        self
+    }
+
+    // the square a domino placed at `position` by `player` would also cover
+    fn second_square(&self, position: Position, player: Player) -> Option<Position>
+    {
+        let other = match player.id
+        {
+            1 => Position {
+                row: position.row + 1,
+                col: position.col,
+            },
+            _ => Position {
+                row: position.row,
+                col: position.col + 1,
+            },
+        };
+
+        match other.row < self.board.rows() && other.col < self.board.cols()
+        {
+            true => Some(other),
+            false => None,
+        }
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<DomineeringMove>
+    {
+        let mut moves = vec![];
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                let position = Position { row, col };
+                if let Some(other) = self.second_square(position, player)
+                {
+                    if self.board[position] == DomineeringCell::Empty
+                        && self.board[other] == DomineeringCell::Empty
+                    {
+                        moves.push(DomineeringMove { position, player });
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn place(mut self, m: DomineeringMove) -> Domineering
+    {
+        let other = self.second_square(m.position, m.player);
+        match other
+        {
+            Some(other)
+                if self.board[m.position] == DomineeringCell::Empty
+                    && self.board[other] == DomineeringCell::Empty =>
+            {
+                self.board[m.position] = DomineeringCell::Covered(m.player);
+                self.board[other] = DomineeringCell::Covered(m.player);
+            },
+            _ => panic!("the move: {m} was not a valid move"),
+        }
+
+        self.to_move = match m.player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        };
+        self.last_move = Some(m);
+        self
+    }
+
+    // a board just big enough for `region`, with every square outside it blocked
+    fn cut_out(&self, region: &[Position]) -> Domineering
+    {
+        let top = region.iter().map(|pos| pos.row).min().unwrap();
+        let left = region.iter().map(|pos| pos.col).min().unwrap();
+        let bottom = region.iter().map(|pos| pos.row).max().unwrap();
+        let right = region.iter().map(|pos| pos.col).max().unwrap();
+
+        let mut game = Domineering::new(bottom - top + 1, right - left + 1);
+        for row in 0..game.board.rows()
+        {
+            for col in 0..game.board.cols()
+            {
+                game.board[Position { row, col }] = DomineeringCell::Blocked;
+            }
+        }
+        for pos in region
+        {
+            game.board[Position {
+                row: pos.row - top,
+                col: pos.col - left,
+            }] = DomineeringCell::Empty;
+        }
+
+        game.to_move = self.to_move;
+        game
+    }
+}
+
+impl GameState for Domineering
+{
+    type Move = DomineeringMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        self.moves_for(self.to_move)
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(self, m: Self::Move) -> Self
+    {
+        self.place(m)
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        match self.moves_for(self.to_move).is_empty()
+        {
+            true => GameResult::Win(Player {
+                id: if self.to_move.id == 1 { 2 } else { 1 },
+            }),
+            false => GameResult::InProgress,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl PartizanGame for Domineering
+{
+    type Key = (usize, usize, Vec<bool>);
+
+    fn position_key(&self) -> Self::Key
+    {
+        let mut empty = vec![];
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                empty.push(self.board[Position { row, col }] == DomineeringCell::Empty);
+            }
+        }
+        (self.board.rows(), self.board.cols(), empty)
+    }
+
+    fn options(&self, player: Player) -> Vec<Self>
+    {
+        self.moves_for(player)
+            .into_iter()
+            .map(|m| self.clone().place(m))
+            .collect()
+    }
+
+    /// Splits the empty squares into regions that are connected horizontally or vertically, each
+    /// cut down to its own smallest board
+    fn components(&self) -> Vec<Self>
+    {
+        let (rows, cols) = (self.board.rows(), self.board.cols());
+        let mut seen = Board::<bool>::new(rows, cols);
+
+        let mut components = vec![];
+        for row in 0..rows
+        {
+            for col in 0..cols
+            {
+                let start = Position { row, col };
+                if seen[start] || self.board[start] != DomineeringCell::Empty
+                {
+                    continue;
+                }
+
+                // flood fill the region
+                seen[start] = true;
+                let mut region = vec![start];
+                let mut stack = vec![start];
+                while let Some(pos) = stack.pop()
+                {
+                    let mut neighbours = vec![];
+                    if pos.row > 0
+                    {
+                        neighbours.push(Position {
+                            row: pos.row - 1,
+                            col: pos.col,
+                        });
+                    }
+                    if pos.col > 0
+                    {
+                        neighbours.push(Position {
+                            row: pos.row,
+                            col: pos.col - 1,
+                        });
+                    }
+                    if pos.row + 1 < rows
+                    {
+                        neighbours.push(Position {
+                            row: pos.row + 1,
+                            col: pos.col,
+                        });
+                    }
+                    if pos.col + 1 < cols
+                    {
+                        neighbours.push(Position {
+                            row: pos.row,
+                            col: pos.col + 1,
+                        });
+                    }
+
+                    for next in neighbours
+                    {
+                        if !seen[next] && self.board[next] == DomineeringCell::Empty
+                        {
+                            seen[next] = true;
+                            region.push(next);
+                            stack.push(next);
+                        }
+                    }
+                }
+
+                // a single square can't be played in, so it's worth nothing
+                if region.len() > 1
+                {
+                    components.push(self.cut_out(&region));
+                }
+            }
+        }
+
+        components
+    }
+}
+
+impl Display for Domineering
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum DomineeringCell
+{
+    #[default]
+    Empty,
+    Covered(Player),
+    Blocked,
+}
+
+impl Display for DomineeringCell
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            DomineeringCell::Empty => write!(f, "-"),
+            DomineeringCell::Covered(player) => write!(f, "{}", player.id),
+            DomineeringCell::Blocked => write!(f, "#"),
+        }
+    }
+}
+
+/// A domino covering `position` and the square below it for player 1, or the square to its right
+/// for player 2
+#[derive(Clone, Copy)]
+pub struct DomineeringMove
+{
+    position: Position,
+    player: Player,
+}
+
+impl Display for DomineeringMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Position: {}", self.player, self.position)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::{
+                board::Position,
+                cgt::{value, Outcome, PartizanGame, Value},
+            },
+            GameResult, GameState, Player,
+        },
+        players::minimax::MinimaxPlayer,
+    };
+
+    use super::Domineering;
+
+    #[test]
+    fn test_known_values()
+    {
+        assert!(value(&Domineering::new(2, 1)) == Value::integer(1));
+        assert!(value(&Domineering::new(1, 4)) == Value::integer(-2));
+        assert!(value(&Domineering::new(1, 1)) == Value::integer(0));
+        assert_eq!(value(&Domineering::new(2, 2)).to_string(), "{1 | -1}");
+
+        // an L shape, where either player can take the only move
+        let corner = Domineering::new(2, 2).set_blocked(Position { row: 0, col: 1 });
+        assert!(value(&corner) == Value::star());
+
+        // a column of 3 with a square next to the bottom is { 0 | 1 }, worth half a move to player 1
+        let game = Domineering::new(3, 2)
+            .set_blocked(Position { row: 0, col: 1 })
+            .set_blocked(Position { row: 1, col: 1 });
+        assert_eq!(value(&game).to_string(), "1/2");
+        assert!(value(&game) + value(&game) + Value::integer(-1) == Value::integer(0));
+    }
+
+    #[test]
+    fn test_regions()
+    {
+        // the blocked column splits the board into a 3x2 and a 3x1 region
+        let mut game = Domineering::new(3, 4);
+        for row in 0..3
+        {
+            game = game.set_blocked(Position { row, col: 2 });
+        }
+        assert_eq!(game.components().len(), 2);
+        assert!(value(&game) == value(&Domineering::new(3, 2)) + value(&Domineering::new(3, 1)));
+    }
+
+    #[test]
+    fn test_minimax_matches_value()
+    {
+        for (rows, cols) in [(2, 2), (2, 3), (3, 2), (3, 3), (1, 5)]
+        {
+            let outcome = value(&Domineering::new(rows, cols)).outcome();
+            for first in [1, 2]
+            {
+                let first = Player::new(first);
+                let second = Player::new(3 - first.id);
+                let expected = match outcome
+                {
+                    Outcome::Left => Player::new(1),
+                    Outcome::Right => Player::new(2),
+                    Outcome::Previous => second,
+                    Outcome::Next => first,
+                };
+
+                let result = Domineering::new(rows, cols).set_first_player(first).play(
+                    &mut MinimaxPlayer::new(None),
+                    &mut MinimaxPlayer::new(None),
+                    false,
+                );
+                assert!(result == GameResult::Win(expected), "{rows}x{cols}");
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::board::{Board, Cell, Position},
+    GameResult, GameState, Player,
+};
+
+/// Dots and Boxes on a grid of `rows` x `cols` boxes.
+///
+/// Players take turns drawing an edge between two neighbouring dots. Drawing the last edge of a
+/// box claims it and gives the player another turn. Once every edge is drawn, the player with the
+/// most boxes wins.
+#[derive(Clone)]
+pub struct DotsAndBoxes
+{
+    // (rows + 1) x cols edges along the top and bottom of each box
+    horizontal: Board<bool>,
+    // rows x (cols + 1) edges along the left and right of each box
+    vertical: Board<bool>,
+    boxes: Board<Cell>,
+    to_move: Player,
+    last_move: Option<DotsAndBoxesMove>,
+}
+
+impl DotsAndBoxes
+{
+    pub fn new(rows: usize, cols: usize) -> DotsAndBoxes
+    {
+        DotsAndBoxes {
+            horizontal: Board::new(rows + 1, cols),
+            vertical: Board::new(rows, cols + 1),
+            boxes: Board::new(rows, cols),
+            to_move: Player { id: 1 },
+            last_move: None,
+        }
+    }
+
+    /// Returns the number of boxes `player` has claimed
+    pub fn score(&self, player: Player) -> usize
+    {
+        self.box_positions()
+            .filter(|&pos| self.boxes[pos] == Cell::Piece(player))
+            .count()
+    }
+
+    fn box_positions(&self) -> impl Iterator<Item = Position>
+    {
+        let cols = self.boxes.cols();
+        (0..self.boxes.rows()).flat_map(move |row| (0..cols).map(move |col| Position { row, col }))
+    }
+
+    fn edges(&self, orientation: Orientation) -> &Board<bool>
+    {
+        match orientation
+        {
+            Orientation::Horizontal => &self.horizontal,
+            Orientation::Vertical => &self.vertical,
+        }
+    }
+
+    fn is_complete(&self, pos: Position) -> bool
+    {
+        self.horizontal[pos]
+            && self.horizontal[Position {
+                row: pos.row + 1,
+                col: pos.col,
+            }]
+            && self.vertical[pos]
+            && self.vertical[Position {
+                row: pos.row,
+                col: pos.col + 1,
+            }]
+    }
+
+    // the boxes on either side of an edge
+    fn adjacent_boxes(&self, m: DotsAndBoxesMove) -> Vec<Position>
+    {
+        let mut boxes = vec![];
+        match m.orientation
+        {
+            Orientation::Horizontal =>
+            {
+                if m.position.row > 0
+                {
+                    boxes.push(Position {
+                        row: m.position.row - 1,
+                        col: m.position.col,
+                    });
+                }
+                if m.position.row < self.boxes.rows()
+                {
+                    boxes.push(m.position);
+                }
+            },
+            Orientation::Vertical =>
+            {
+                if m.position.col > 0
+                {
+                    boxes.push(Position {
+                        row: m.position.row,
+                        col: m.position.col - 1,
+                    });
+                }
+                if m.position.col < self.boxes.cols()
+                {
+                    boxes.push(m.position);
+                }
+            },
+        }
+        boxes
+    }
+}
+
+impl GameState for DotsAndBoxes
+{
+    type Move = DotsAndBoxesMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        let mut moves = vec![];
+        for orientation in [Orientation::Horizontal, Orientation::Vertical]
+        {
+            let edges = self.edges(orientation);
+            for row in 0..edges.rows()
+            {
+                for col in 0..edges.cols()
+                {
+                    let position = Position { row, col };
+                    if !edges[position]
+                    {
+                        moves.push(DotsAndBoxesMove {
+                            orientation,
+                            position,
+                            player: self.to_move,
+                        });
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        let edge = match m.orientation
+        {
+            Orientation::Horizontal => &mut self.horizontal[m.position],
+            Orientation::Vertical => &mut self.vertical[m.position],
+        };
+        if *edge
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+        *edge = true;
+
+        let mut completed_box = false;
+        for pos in self.adjacent_boxes(m)
+        {
+            if self.is_complete(pos)
+            {
+                self.boxes[pos] = Cell::Piece(m.player);
+                completed_box = true;
+            }
+        }
+
+        // completing a box gives the player another turn
+        if !completed_box
+        {
+            self.to_move = match m.player.id
+            {
+                1 => Player { id: 2 },
+                _ => Player { id: 1 },
+            };
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if self
+            .box_positions()
+            .any(|pos| self.boxes[pos] == Cell::Empty)
+        {
+            return GameResult::InProgress;
+        }
+
+        let p1 = self.score(Player { id: 1 });
+        let p2 = self.score(Player { id: 2 });
+        match p1.cmp(&p2)
+        {
+            std::cmp::Ordering::Greater => GameResult::Win(Player { id: 1 }),
+            std::cmp::Ordering::Less => GameResult::Win(Player { id: 2 }),
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for DotsAndBoxes
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        for row in 0..self.horizontal.rows()
+        {
+            for col in 0..self.horizontal.cols()
+            {
+                match self.horizontal[Position { row, col }]
+                {
+                    true => write!(f, "+---")?,
+                    false => write!(f, "+   ")?,
+                }
+            }
+            writeln!(f, "+")?;
+
+            if row == self.boxes.rows()
+            {
+                break;
+            }
+
+            for col in 0..self.vertical.cols()
+            {
+                match self.vertical[Position { row, col }]
+                {
+                    true => write!(f, "|")?,
+                    false => write!(f, " ")?,
+                }
+                if col < self.boxes.cols()
+                {
+                    match self.boxes[Position { row, col }]
+                    {
+                        Cell::Empty => write!(f, "   ")?,
+                        owner => write!(f, " {owner} ")?,
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Orientation
+{
+    Horizontal,
+    Vertical,
+}
+
+impl Display for Orientation
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Orientation::Horizontal => write!(f, "Horizontal"),
+            Orientation::Vertical => write!(f, "Vertical"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DotsAndBoxesMove
+{
+    orientation: Orientation,
+    // horizontal edges are indexed by the box below them and vertical edges by the box to their
+    // right, so the edges along the bottom and right of the grid are one past the last box
+    position: Position,
+    player: Player,
+}
+
+impl Display for DotsAndBoxesMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}, {} Edge: {}",
+            self.player, self.orientation, self.position
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{common::board::Position, GameResult, GameState, Player};
+
+    use super::{DotsAndBoxes, DotsAndBoxesMove, Orientation};
+
+    fn do_move(game: DotsAndBoxes, orientation: Orientation, row: usize, col: usize)
+        -> DotsAndBoxes
+    {
+        let player = game.player_to_move();
+        game.do_move(DotsAndBoxesMove {
+            orientation,
+            position: Position { row, col },
+            player,
+        })
+    }
+
+    #[test]
+    fn test_completing_box()
+    {
+        let mut game = DotsAndBoxes::new(1, 2);
+        assert_eq!(game.get_valid_moves().len(), 7);
+
+        game = do_move(game, Orientation::Horizontal, 0, 0);
+        game = do_move(game, Orientation::Horizontal, 1, 0);
+        game = do_move(game, Orientation::Vertical, 0, 0);
+        assert!(game.player_to_move() == Player::new(2));
+
+        // player 2 closes the left box and moves again
+        game = do_move(game, Orientation::Vertical, 0, 1);
+        assert_eq!(game.score(Player::new(2)), 1);
+        assert!(game.player_to_move() == Player::new(2));
+
+        // the extra move doesn't close a box, so the turn passes to player 1, who draws the third
+        // side of the right box and lets player 2 close it
+        game = do_move(game, Orientation::Horizontal, 0, 1);
+        assert!(game.player_to_move() == Player::new(1));
+        game = do_move(game, Orientation::Horizontal, 1, 1);
+        assert!(game.player_to_move() == Player::new(2));
+        game = do_move(game, Orientation::Vertical, 0, 2);
+
+        assert_eq!(game.score(Player::new(2)), 2);
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+}
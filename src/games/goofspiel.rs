@@ -0,0 +1,220 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{GameResult, Player, SimultaneousGame};
+
+/// Goofspiel, where each player has the cards 1 to n and bids on prize cards worth 1 to n.
+///
+/// Each round reveals the next prize and both players secretly play one of their cards. The
+/// higher card wins the prize, and a tie means nobody gets it. Once every prize has been bid on,
+/// the player with the most points wins.
+#[derive(Clone)]
+pub struct Goofspiel
+{
+    hands: [Vec<usize>; 2],
+    // prizes still to be bid on, with the current one first
+    prizes: Vec<usize>,
+    scores: [usize; 2],
+    last_moves: Option<(GoofspielMove, GoofspielMove)>,
+}
+
+impl Goofspiel
+{
+    /// Goofspiel with `cards` cards, where the prizes are bid on from highest to lowest
+    pub fn new(cards: usize) -> Goofspiel
+    {
+        Goofspiel {
+            hands: [(1..=cards).collect(), (1..=cards).collect()],
+            prizes: (1..=cards).rev().collect(),
+            scores: [0, 0],
+            last_moves: None,
+        }
+    }
+
+    /// Sets the order the prizes are bid on, which has to be some order of 1 to n
+    pub fn set_prizes(mut self, prizes: Vec<usize>) -> Goofspiel
+    {
+        let mut sorted = prizes.clone();
+        sorted.sort_unstable();
+        if sorted != self.hands[0]
+        {
+            panic!("prizes have to be the cards 1 to {}", self.hands[0].len());
+        }
+
+        self.prizes = prizes;
+        self
+    }
+
+    /// Returns the total value of the prizes `player` has won
+    pub fn score(&self, player: Player) -> usize
+    {
+        self.scores[player.id - 1]
+    }
+}
+
+impl SimultaneousGame for Goofspiel
+{
+    type Move = GoofspielMove;
+
+    fn get_valid_moves(&self, player: Player) -> Vec<Self::Move>
+    {
+        self.hands[player.id - 1]
+            .iter()
+            .map(|&card| GoofspielMove { card, player })
+            .collect()
+    }
+
+    fn do_moves(mut self, p1_move: Self::Move, p2_move: Self::Move) -> Self
+    {
+        if p1_move.player.id != 1
+        {
+            panic!("the move: {p1_move} was not a valid move");
+        }
+        if p2_move.player.id != 2
+        {
+            panic!("the move: {p2_move} was not a valid move");
+        }
+
+        for m in [p1_move, p2_move]
+        {
+            let hand = &mut self.hands[m.player.id - 1];
+            match hand.iter().position(|&card| card == m.card)
+            {
+                Some(index) => hand.remove(index),
+                None => panic!("the move: {m} was not a valid move"),
+            };
+        }
+
+        let prize = self.prizes.remove(0);
+        match p1_move.card.cmp(&p2_move.card)
+        {
+            std::cmp::Ordering::Greater => self.scores[0] += prize,
+            std::cmp::Ordering::Less => self.scores[1] += prize,
+            std::cmp::Ordering::Equal => (),
+        }
+        self.last_moves = Some((p1_move, p2_move));
+
+        self
+    }
+
+    fn last_moves(&self) -> Option<(Self::Move, Self::Move)>
+    {
+        self.last_moves
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if !self.prizes.is_empty()
+        {
+            return GameResult::InProgress;
+        }
+
+        match self.scores[0].cmp(&self.scores[1])
+        {
+            std::cmp::Ordering::Greater => GameResult::Win(Player { id: 1 }),
+            std::cmp::Ordering::Less => GameResult::Win(Player { id: 2 }),
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+}
+
+impl Display for Goofspiel
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Scores: {} - {}", self.scores[0], self.scores[1])?;
+        if let Some((p1_move, p2_move)) = self.last_moves
+        {
+            writeln!(f, "Last Bids: {} - {}", p1_move.card, p2_move.card)?;
+        }
+        match self.prizes.first()
+        {
+            Some(prize) => writeln!(f, "Prize: {prize}")?,
+            None => writeln!(f, "Result: {}", self.check_win())?,
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct GoofspielMove
+{
+    card: usize,
+    player: Player,
+}
+
+impl Display for GoofspielMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Card: {}", self.player, self.card)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{GameResult, Player, SimultaneousGame},
+        players::random::RandomPlayer,
+    };
+
+    use super::{Goofspiel, GoofspielMove};
+
+    #[test]
+    fn test_bidding()
+    {
+        let game = Goofspiel::new(3);
+        assert_eq!(game.get_valid_moves(Player::new(1)).len(), 3);
+
+        let bid = |game: Goofspiel, p1: usize, p2: usize| {
+            game.do_moves(
+                GoofspielMove {
+                    card: p1,
+                    player: Player::new(1),
+                },
+                GoofspielMove {
+                    card: p2,
+                    player: Player::new(2),
+                },
+            )
+        };
+
+        // player 1 wins the 3, the tied 2 is discarded, and player 2 wins the 1
+        let game = bid(game, 3, 1);
+        assert_eq!(game.get_valid_moves(Player::new(1)).len(), 2);
+        let game = bid(game, 2, 2);
+        let game = bid(game, 1, 3);
+        assert_eq!(game.score(Player::new(1)), 3);
+        assert_eq!(game.score(Player::new(2)), 1);
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swapped_moves()
+    {
+        let game = Goofspiel::new(3);
+        game.do_moves(
+            GoofspielMove {
+                card: 1,
+                player: Player::new(2),
+            },
+            GoofspielMove {
+                card: 2,
+                player: Player::new(1),
+            },
+        );
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let game = Goofspiel::new(13).set_prizes((1..=13).collect());
+        let result = game.play(
+            &mut RandomPlayer::from_seed(0),
+            &mut RandomPlayer::from_seed(1),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{GameResult, GameState, Player};
+
+/// Kalah, the most common mancala sowing game.
+///
+/// Each player has a row of pits and a store to the right of them. A move picks up every seed in
+/// one of your pits and sows them one at a time counterclockwise, skipping the opponent's store.
+/// Ending in your own store gives you another turn, and ending in one of your own empty pits
+/// captures that seed along with the seeds in the opposite pit. The game ends when one player has
+/// no seeds left in their pits, and the player with more seeds in their store wins.
+#[derive(Clone)]
+pub struct Kalah
+{
+    // player 1's pits, player 1's store, player 2's pits, player 2's store
+    seeds: Vec<usize>,
+    pits: usize,
+    to_move: Player,
+    last_move: Option<KalahMove>,
+}
+
+impl Kalah
+{
+    pub fn new(pits: usize, seeds: usize) -> Kalah
+    {
+        let mut board = vec![seeds; 2 * pits + 2];
+        board[pits] = 0;
+        board[2 * pits + 1] = 0;
+
+        Kalah {
+            seeds: board,
+            pits,
+            to_move: Player { id: 1 },
+            last_move: None,
+        }
+    }
+
+    /// Returns the number of seeds in `player`'s store
+    pub fn score(&self, player: Player) -> usize
+    {
+        self.seeds[self.store(player)]
+    }
+
+    fn store(&self, player: Player) -> usize
+    {
+        match player.id
+        {
+            1 => self.pits,
+            _ => 2 * self.pits + 1,
+        }
+    }
+
+    // index into self.seeds of one of the player's pits
+    fn pit(&self, player: Player, pit: usize) -> usize
+    {
+        match player.id
+        {
+            1 => pit,
+            _ => self.pits + 1 + pit,
+        }
+    }
+
+    fn side_is_empty(&self, player: Player) -> bool
+    {
+        (0..self.pits).all(|pit| self.seeds[self.pit(player, pit)] == 0)
+    }
+
+    fn opponent(player: Player) -> Player
+    {
+        match player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        }
+    }
+}
+
+impl GameState for Kalah
+{
+    type Move = KalahMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        (0..self.pits)
+            .filter(|&pit| self.seeds[self.pit(self.to_move, pit)] > 0)
+            .map(|pit| KalahMove {
+                pit,
+                player: self.to_move,
+            })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        let opponent = Kalah::opponent(m.player);
+        let opponent_store = self.store(opponent);
+
+        let mut index = self.pit(m.player, m.pit);
+        let mut in_hand = self.seeds[index];
+        self.seeds[index] = 0;
+
+        if in_hand == 0
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        while in_hand > 0
+        {
+            index = (index + 1) % self.seeds.len();
+            if index == opponent_store
+            {
+                continue;
+            }
+
+            self.seeds[index] += 1;
+            in_hand -= 1;
+        }
+
+        // capture if the last seed landed in one of the player's own pits that was empty
+        let own_pits = self.pit(m.player, 0)..self.pit(m.player, 0) + self.pits;
+        if own_pits.contains(&index) && self.seeds[index] == 1
+        {
+            // pits are numbered in opposite directions on each side, so the opposite pit mirrors the index
+            let opposite = 2 * self.pits - index;
+            if self.seeds[opposite] > 0
+            {
+                let store = self.store(m.player);
+                self.seeds[store] += self.seeds[opposite] + 1;
+                self.seeds[opposite] = 0;
+                self.seeds[index] = 0;
+            }
+        }
+
+        // once either side runs out of seeds, the seeds left on the other side go to their owner's store
+        if self.side_is_empty(m.player) || self.side_is_empty(opponent)
+        {
+            for player in [m.player, opponent]
+            {
+                let store = self.store(player);
+                for pit in 0..self.pits
+                {
+                    let pit_index = self.pit(player, pit);
+                    self.seeds[store] += self.seeds[pit_index];
+                    self.seeds[pit_index] = 0;
+                }
+            }
+        }
+
+        // ending in your own store gives you another turn
+        if index != self.store(m.player)
+        {
+            self.to_move = opponent;
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if !self.side_is_empty(Player { id: 1 }) || !self.side_is_empty(Player { id: 2 })
+        {
+            return GameResult::InProgress;
+        }
+
+        let p1 = self.score(Player { id: 1 });
+        let p2 = self.score(Player { id: 2 });
+        match p1.cmp(&p2)
+        {
+            std::cmp::Ordering::Greater => GameResult::Win(Player { id: 1 }),
+            std::cmp::Ordering::Less => GameResult::Win(Player { id: 2 }),
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Kalah
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        // player 2's side is printed right to left along the top so that sowing goes counterclockwise
+        writeln!(f, "Board: ")?;
+        write!(f, "   ")?;
+        for pit in (0..self.pits).rev()
+        {
+            write!(f, "{:>3}", self.seeds[self.pit(Player { id: 2 }, pit)])?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>3}{}{:>3}",
+            self.score(Player { id: 2 }),
+            " ".repeat(3 * self.pits),
+            self.score(Player { id: 1 })
+        )?;
+        write!(f, "   ")?;
+        for pit in 0..self.pits
+        {
+            write!(f, "{:>3}", self.seeds[self.pit(Player { id: 1 }, pit)])?;
+        }
+        writeln!(f)?;
+
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct KalahMove
+{
+    // pits are numbered from 0 in the direction the seeds are sown
+    pit: usize,
+    player: Player,
+}
+
+impl Display for KalahMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Pit: {}", self.player, self.pit)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{GameResult, GameState, Player};
+
+    use super::{Kalah, KalahMove};
+
+    fn do_move(game: Kalah, pit: usize) -> Kalah
+    {
+        let player = game.player_to_move();
+        game.do_move(KalahMove { pit, player })
+    }
+
+    #[test]
+    fn test_extra_turn()
+    {
+        // 4 seeds from the third of 6 pits ends exactly in the store
+        let game = do_move(Kalah::new(6, 4), 2);
+        assert_eq!(game.score(Player::new(1)), 1);
+        assert!(game.player_to_move() == Player::new(1));
+
+        let game = do_move(game, 0);
+        assert!(game.player_to_move() == Player::new(2));
+    }
+
+    #[test]
+    fn test_capture()
+    {
+        let mut game = Kalah::new(6, 4);
+        game.seeds = vec![1, 0, 4, 4, 4, 4, 0, 4, 4, 4, 4, 4, 4, 0];
+
+        // the single seed lands in the empty second pit, capturing the 4 seeds opposite it
+        let game = do_move(game, 0);
+        assert_eq!(game.score(Player::new(1)), 5);
+        assert_eq!(game.seeds[1], 0);
+        assert_eq!(game.seeds[11], 0);
+        assert!(game.player_to_move() == Player::new(2));
+    }
+
+    #[test]
+    fn test_result()
+    {
+        let mut game = Kalah::new(1, 1);
+        game = do_move(game, 0);
+
+        // player 1 sows into their store and their side is empty, so player 2 collects their seed
+        assert!(game.check_win() == GameResult::Draw);
+        assert!(game.get_valid_moves().is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Cell, Position},
+        generate_line, on_board, tuple_to_pos,
+    },
+    GameResult, GameState, Player,
+};
+
+/// Lines of Action on an 8x8 board. Player 1 starts along the top and bottom edges and player 2
+/// along the left and right edges, without the corners.
+///
+/// A piece moves in a straight line exactly as many squares as there are pieces, of either colour,
+/// on that line. It can jump over its own pieces but not the opponent's, and captures by landing
+/// on an opponent's piece. The first player to get all of their pieces into one group, connected
+/// orthogonally or diagonally, wins. If a move connects both groups, the player who moved wins. A
+/// player without a move loses.
+#[derive(Clone)]
+pub struct LinesOfAction
+{
+    board: Board<Cell>,
+    last_move: Option<LinesOfActionMove>,
+}
+
+const DIRECTIONS: [(i128, i128); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl LinesOfAction
+{
+    pub fn new() -> LinesOfAction
+    {
+        let mut board = Board::new(8, 8);
+        for i in 1..7
+        {
+            board[Position { row: 0, col: i }] = Cell::Piece(Player { id: 1 });
+            board[Position { row: 7, col: i }] = Cell::Piece(Player { id: 1 });
+            board[Position { row: i, col: 0 }] = Cell::Piece(Player { id: 2 });
+            board[Position { row: i, col: 7 }] = Cell::Piece(Player { id: 2 });
+        }
+
+        LinesOfAction {
+            board,
+            last_move: None,
+        }
+    }
+
+    fn opponent(player: Player) -> Player
+    {
+        match player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position>
+    {
+        let cols = self.board.cols();
+        (0..self.board.rows()).flat_map(move |row| (0..cols).map(move |col| Position { row, col }))
+    }
+
+    // where the piece at `from` lands moving in `dir`, if it can move that way
+    fn destination(&self, from: Position, dir: (i128, i128), player: Player) -> Option<Position>
+    {
+        let size = (self.board.rows(), self.board.cols());
+
+        // the distance is the number of pieces on the whole line, in both directions
+        let distance = generate_line(from, dir, size)
+            .into_iter()
+            .filter(|&pos| self.board[pos] != Cell::Empty)
+            .count() as i128;
+
+        let to = (
+            from.row as i128 + dir.0 * distance,
+            from.col as i128 + dir.1 * distance,
+        );
+        if !on_board(to, size) || self.board[tuple_to_pos(to)] == Cell::Piece(player)
+        {
+            return None;
+        }
+
+        // own pieces can be jumped, but the opponent's can't
+        let blocked = (1..distance).any(|step| {
+            let pos = tuple_to_pos((
+                from.row as i128 + dir.0 * step,
+                from.col as i128 + dir.1 * step,
+            ));
+            self.board[pos] == Cell::Piece(LinesOfAction::opponent(player))
+        });
+        match blocked
+        {
+            true => None,
+            false => Some(tuple_to_pos(to)),
+        }
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<LinesOfActionMove>
+    {
+        let mut moves = vec![];
+        for from in self.positions()
+        {
+            if self.board[from] != Cell::Piece(player)
+            {
+                continue;
+            }
+
+            for dir in DIRECTIONS
+            {
+                if let Some(to) = self.destination(from, dir, player)
+                {
+                    moves.push(LinesOfActionMove { from, to, player });
+                }
+            }
+        }
+
+        moves
+    }
+
+    // the player who has connected all of their pieces, checking the player who just moved first
+    // so they win if both groups are connected
+    fn connected_player(&self) -> Option<Player>
+    {
+        let player_to_move = self.player_to_move();
+        let last_player = LinesOfAction::opponent(player_to_move);
+        if self.last_move.is_some() && self.is_connected(last_player)
+        {
+            return Some(last_player);
+        }
+        if self.is_connected(player_to_move)
+        {
+            return Some(player_to_move);
+        }
+
+        None
+    }
+
+    /// Whether all of `player`'s pieces form a single group
+    pub fn is_connected(&self, player: Player) -> bool
+    {
+        let size = (self.board.rows(), self.board.cols());
+        let pieces: Vec<Position> = self
+            .positions()
+            .filter(|&pos| self.board[pos] == Cell::Piece(player))
+            .collect();
+        let Some(&start) = pieces.first()
+        else
+        {
+            return false;
+        };
+
+        // flood fill from one piece and check that it reaches all of them
+        let mut seen = vec![start];
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop()
+        {
+            for dir in DIRECTIONS
+            {
+                let next = (pos.row as i128 + dir.0, pos.col as i128 + dir.1);
+                if !on_board(next, size)
+                {
+                    continue;
+                }
+
+                let next = tuple_to_pos(next);
+                if self.board[next] == Cell::Piece(player) && !seen.contains(&next)
+                {
+                    seen.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+
+        seen.len() == pieces.len()
+    }
+}
+
+impl Default for LinesOfAction
+{
+    fn default() -> Self
+    {
+        LinesOfAction::new()
+    }
+}
+
+impl GameState for LinesOfAction
+{
+    type Move = LinesOfActionMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        // a player without a move has lost, so there is no need to check for that first
+        if self.connected_player().is_some()
+        {
+            return vec![];
+        }
+
+        self.moves_for(self.player_to_move())
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) => LinesOfAction::opponent(last_move.player),
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if m.player != self.player_to_move()
+            || self.board[m.from] != Cell::Piece(m.player)
+            || !DIRECTIONS
+                .iter()
+                .any(|&dir| self.destination(m.from, dir, m.player) == Some(m.to))
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board[m.to] = Cell::Piece(m.player);
+        self.board[m.from] = Cell::Empty;
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if let Some(player) = self.connected_player()
+        {
+            return GameResult::Win(player);
+        }
+
+        let player_to_move = self.player_to_move();
+        if self.moves_for(player_to_move).is_empty()
+        {
+            return GameResult::Win(LinesOfAction::opponent(player_to_move));
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for LinesOfAction
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LinesOfActionMove
+{
+    from: Position,
+    to: Position,
+    player: Player,
+}
+
+impl Display for LinesOfActionMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, From: {}, To: {}", self.player, self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::board::{Board, Cell, Position},
+            GameResult, GameState, Player,
+        },
+        players::random::RandomPlayer,
+    };
+
+    use super::{LinesOfAction, LinesOfActionMove};
+
+    fn empty_game(pieces: &[(usize, usize, usize)]) -> LinesOfAction
+    {
+        let mut game = LinesOfAction {
+            board: Board::new(8, 8),
+            last_move: None,
+        };
+        for &(row, col, id) in pieces
+        {
+            game.board[Position { row, col }] = Cell::Piece(Player::new(id));
+        }
+        game
+    }
+
+    fn destinations(game: &LinesOfAction, row: usize, col: usize) -> Vec<(usize, usize)>
+    {
+        let mut destinations: Vec<(usize, usize)> = game
+            .get_valid_moves()
+            .iter()
+            .filter(|m| m.from == Position { row, col })
+            .map(|m| (m.to.row, m.to.col))
+            .collect();
+        destinations.sort_unstable();
+        destinations
+    }
+
+    #[test]
+    fn test_opening_moves()
+    {
+        assert_eq!(LinesOfAction::new().get_valid_moves().len(), 36);
+    }
+
+    #[test]
+    fn test_line_counts()
+    {
+        // the piece at (4, 2) has 3 pieces on its row, 2 on its column and 1 on each diagonal
+        let game = empty_game(&[(4, 2, 1), (4, 3, 1), (4, 6, 2), (6, 2, 1), (0, 0, 2)]);
+        assert_eq!(
+            destinations(&game, 4, 2),
+            vec![(2, 2), (3, 1), (3, 3), (4, 5), (5, 1), (5, 3)]
+        );
+
+        // it can capture the opponent's piece on its row, but can't jump the one on its column
+        let game = empty_game(&[
+            (4, 2, 1),
+            (0, 7, 1),
+            (4, 4, 2),
+            (5, 2, 2),
+            (0, 0, 2),
+            (7, 7, 2),
+        ]);
+        assert_eq!(
+            destinations(&game, 4, 2),
+            vec![(2, 2), (3, 1), (3, 3), (4, 0), (4, 4), (5, 1), (5, 3)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_jumping_opponent()
+    {
+        // there are two pieces on the column, but the opponent's is in the way
+        let game = empty_game(&[(4, 2, 1), (5, 2, 2), (0, 0, 2), (7, 7, 1)]);
+        game.do_move(LinesOfActionMove {
+            from: Position { row: 4, col: 2 },
+            to: Position { row: 6, col: 2 },
+            player: Player::new(1),
+        });
+    }
+
+    #[test]
+    fn test_connection_wins()
+    {
+        let game = empty_game(&[(0, 0, 1), (2, 0, 1), (1, 3, 2), (7, 7, 2)]);
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // joining up the two pieces wins
+        let game = game.do_move(LinesOfActionMove {
+            from: Position { row: 2, col: 0 },
+            to: Position { row: 1, col: 1 },
+            player: Player::new(1),
+        });
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+
+        // capturing the last unconnected piece connects both players, and the mover wins
+        let game = empty_game(&[(0, 0, 1), (3, 1, 1), (1, 1, 2), (5, 5, 2), (5, 6, 2)]);
+        let game = game.do_move(LinesOfActionMove {
+            from: Position { row: 3, col: 1 },
+            to: Position { row: 1, col: 1 },
+            player: Player::new(1),
+        });
+        assert!(game.is_connected(Player::new(2)));
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        for seed in 0..5
+        {
+            let result = LinesOfAction::new().play(
+                &mut RandomPlayer::from_seed(seed),
+                &mut RandomPlayer::from_seed(seed + 100),
+                false,
+            );
+            assert!(result != GameResult::Draw);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{GameResult, GameState, Player};
+
+/// Misère version of any two player game. Moves are the same as the inner game, but whoever meets
+/// the winning condition loses instead.
+#[derive(Clone)]
+pub struct Misere<G: GameState>
+{
+    game: G,
+}
+
+impl<G: GameState> Misere<G>
+{
+    pub fn new(game: G) -> Misere<G>
+    {
+        Misere { game }
+    }
+
+    pub fn inner(&self) -> &G
+    {
+        &self.game
+    }
+}
+
+impl<G: GameState> GameState for Misere<G>
+{
+    type Move = G::Move;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        self.game.get_valid_moves()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.game.player_to_move()
+    }
+
+    fn do_move(self, m: Self::Move) -> Self
+    {
+        Misere {
+            game: self.game.do_move(m),
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.game.last_move()
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        match self.game.check_win()
+        {
+            GameResult::Win(player) => GameResult::Win(Player {
+                id: if player.id == 1 { 2 } else { 1 },
+            }),
+            result => result,
+        }
+    }
+
+    fn chance_outcomes(&self) -> Option<Vec<(Self::Move, f64)>>
+    {
+        self.game.chance_outcomes()
+    }
+}
+
+impl<G: GameState> Display for Misere<G>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        // the inner game prints its own result, so add the real one after it
+        write!(f, "{}", self.game)?;
+        if self.check_win() != GameResult::InProgress
+        {
+            writeln!(f, "Misère Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::board::Position,
+            connect4::Connect4,
+            tictactoe::{TicTacToe, TicTacToeMove},
+            GameResult, GameState, Player,
+        },
+        players::{mcts::MCTSPlayer, minimax::MinimaxPlayer, random::RandomPlayer, GamePlayer},
+    };
+
+    use super::Misere;
+
+    #[test]
+    fn test_swapped_result()
+    {
+        let mut game = Misere::new(TicTacToe::new(3, 3, 3));
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]
+        {
+            let player = game.player_to_move();
+            game = game.do_move(TicTacToeMove {
+                position: Position { row, col },
+                player,
+            });
+        }
+
+        // player 1 completed the top row, so they lose
+        assert!(game.inner().check_win() == GameResult::Win(Player::new(1)));
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    fn test_perfect_play()
+    {
+        // misère tic-tac-toe is a draw, the same as the normal game
+        let result = Misere::new(TicTacToe::new(3, 3, 3)).play(
+            &mut MinimaxPlayer::new(None),
+            &mut MinimaxPlayer::new(None),
+            false,
+        );
+        assert!(result == GameResult::Draw);
+    }
+
+    // drops a piece in `col`, which is the column at the end of the move's position
+    fn drop_in(game: Misere<Connect4>, col: usize) -> Misere<Connect4>
+    {
+        let m = *game
+            .get_valid_moves()
+            .iter()
+            .find(|m| m.to_string().ends_with(&format!(", {col})")))
+            .expect("column should be open");
+        game.do_move(m)
+    }
+
+    #[test]
+    fn test_connect4()
+    {
+        let result = Misere::new(Connect4::new(6, 7, 4)).play(
+            &mut MCTSPlayer::from_seed(100, 0),
+            &mut RandomPlayer::from_seed(0),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_mcts_avoids_four()
+    {
+        // player 1 has three along the bottom row, so dropping in column 3 would make four and
+        // lose straight away
+        let mut game = Misere::new(Connect4::new(6, 7, 4));
+        for col in [0, 0, 1, 1, 2, 6]
+        {
+            game = drop_in(game, col);
+        }
+        assert!(drop_in(game.clone(), 3).check_win() == GameResult::Win(Player::new(2)));
+
+        let m = MCTSPlayer::from_seed(500, 0).choose_move(&game);
+        assert!(game.do_move(m).check_win() == GameResult::InProgress, "{m}");
+    }
+}
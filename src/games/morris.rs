@@ -0,0 +1,577 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{common::board::Cell, GameResult, GameState, Player};
+
+/// Nine Men's Morris. The 24 points are numbered left to right and then top to bottom:
+///
+/// ```text
+/// 0-----------1-----------2
+/// |           |           |
+/// |   3-------4-------5   |
+/// |   |       |       |   |
+/// |   |   6---7---8   |   |
+/// |   |   |       |   |   |
+/// 9---10--11      12--13--14
+/// |   |   |       |   |   |
+/// |   |   15--16--17  |   |
+/// |   |       |       |   |
+/// |   18------19------20  |
+/// |           |           |
+/// 21----------22----------23
+/// ```
+///
+/// Players first take turns placing their 9 pieces, then move pieces along the lines to an
+/// adjacent empty point. A player down to 3 pieces can fly a piece to any empty point. Getting
+/// three in a row along a line forms a mill, which removes one of the opponent's pieces as part of
+/// the same move. A player with fewer than 3 pieces, or without a move, loses.
+#[derive(Clone)]
+pub struct Morris
+{
+    points: [Cell; 24],
+    in_hand: [usize; 2],
+    // moves in a row without a piece being removed, to stop games from going on forever
+    moves_without_removal: usize,
+    last_move: Option<MorrisMove>,
+}
+
+const ADJACENT: [&[usize]; 24] = [
+    &[1, 9],
+    &[0, 2, 4],
+    &[1, 14],
+    &[4, 10],
+    &[1, 3, 5, 7],
+    &[4, 13],
+    &[7, 11],
+    &[4, 6, 8],
+    &[7, 12],
+    &[0, 10, 21],
+    &[3, 9, 11, 18],
+    &[6, 10, 15],
+    &[8, 13, 17],
+    &[5, 12, 14, 20],
+    &[2, 13, 23],
+    &[11, 16],
+    &[15, 17, 19],
+    &[12, 16],
+    &[10, 19],
+    &[16, 18, 20, 22],
+    &[13, 19],
+    &[9, 22],
+    &[19, 21, 23],
+    &[14, 22],
+];
+
+const MILLS: [[usize; 3]; 16] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [9, 10, 11],
+    [12, 13, 14],
+    [15, 16, 17],
+    [18, 19, 20],
+    [21, 22, 23],
+    [0, 9, 21],
+    [3, 10, 18],
+    [6, 11, 15],
+    [1, 4, 7],
+    [16, 19, 22],
+    [8, 12, 17],
+    [5, 13, 20],
+    [2, 14, 23],
+];
+
+/// The game is a draw after this many moves in a row without a piece being removed
+const MAX_MOVES_WITHOUT_REMOVAL: usize = 100;
+
+impl Morris
+{
+    pub fn new() -> Morris
+    {
+        Morris {
+            points: [Cell::Empty; 24],
+            in_hand: [9, 9],
+            moves_without_removal: 0,
+            last_move: None,
+        }
+    }
+
+    fn opponent(player: Player) -> Player
+    {
+        match player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        }
+    }
+
+    fn on_board(&self, player: Player) -> usize
+    {
+        self.points
+            .iter()
+            .filter(|&&cell| cell == Cell::Piece(player))
+            .count()
+    }
+
+    fn in_mill(points: &[Cell; 24], point: usize) -> bool
+    {
+        let cell = points[point];
+        cell != Cell::Empty
+            && MILLS
+                .iter()
+                .any(|mill| mill.contains(&point) && mill.iter().all(|&p| points[p] == cell))
+    }
+
+    // the opponent's pieces that a mill can remove, which can only be in a mill if every piece is
+    // in one
+    fn removable(points: &[Cell; 24], opponent: Player) -> Vec<usize>
+    {
+        let opponent_points: Vec<usize> = (0..24)
+            .filter(|&p| points[p] == Cell::Piece(opponent))
+            .collect();
+        let outside_mills: Vec<usize> = opponent_points
+            .iter()
+            .copied()
+            .filter(|&p| !Morris::in_mill(points, p))
+            .collect();
+        match outside_mills.is_empty()
+        {
+            true => opponent_points,
+            false => outside_mills,
+        }
+    }
+
+    // the (from, to) pairs the player can move, with from being None while placing
+    fn steps(&self, player: Player) -> Vec<(Option<usize>, usize)>
+    {
+        let empty: Vec<usize> = (0..24).filter(|&p| self.points[p] == Cell::Empty).collect();
+
+        if self.in_hand[player.id - 1] > 0
+        {
+            return empty.into_iter().map(|to| (None, to)).collect();
+        }
+
+        let flying = self.on_board(player) == 3;
+        let mut steps = vec![];
+        for from in (0..24).filter(|&p| self.points[p] == Cell::Piece(player))
+        {
+            for &to in &empty
+            {
+                if flying || ADJACENT[from].contains(&to)
+                {
+                    steps.push((Some(from), to));
+                }
+            }
+        }
+
+        steps
+    }
+}
+
+impl Default for Morris
+{
+    fn default() -> Self
+    {
+        Morris::new()
+    }
+}
+
+impl GameState for Morris
+{
+    type Move = MorrisMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let player = self.player_to_move();
+        let opponent = Morris::opponent(player);
+
+        let mut moves = vec![];
+        for (from, to) in self.steps(player)
+        {
+            let mut points = self.points;
+            if let Some(from) = from
+            {
+                points[from] = Cell::Empty;
+            }
+            points[to] = Cell::Piece(player);
+
+            if !Morris::in_mill(&points, to)
+            {
+                moves.push(MorrisMove {
+                    from,
+                    to,
+                    remove: None,
+                    player,
+                });
+                continue;
+            }
+
+            for remove in Morris::removable(&points, opponent)
+            {
+                moves.push(MorrisMove {
+                    from,
+                    to,
+                    remove: Some(remove),
+                    player,
+                });
+            }
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) => Morris::opponent(last_move.player),
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        // every piece in hand has to be placed before any can move, and pieces move along the
+        // lines unless the player is down to 3
+        let i = m.player.id - 1;
+        let valid_step = m.player == self.player_to_move()
+            && m.to < 24
+            && self.points[m.to] == Cell::Empty
+            && match m.from
+            {
+                None => self.in_hand[i] > 0,
+                Some(from) =>
+                {
+                    self.in_hand[i] == 0
+                        && from < 24
+                        && self.points[from] == Cell::Piece(m.player)
+                        && (self.on_board(m.player) == 3 || ADJACENT[from].contains(&m.to))
+                },
+            };
+        if !valid_step
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        match m.from
+        {
+            Some(from) => self.points[from] = Cell::Empty,
+            None => self.in_hand[i] -= 1,
+        }
+        self.points[m.to] = Cell::Piece(m.player);
+
+        // forming a mill has to remove one of the opponent's pieces, and nothing else can
+        let valid_removal = match (Morris::in_mill(&self.points, m.to), m.remove)
+        {
+            (true, Some(remove)) =>
+            {
+                Morris::removable(&self.points, Morris::opponent(m.player)).contains(&remove)
+            },
+            (false, None) => true,
+            _ => false,
+        };
+        if !valid_removal
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        match m.remove
+        {
+            Some(remove) =>
+            {
+                self.points[remove] = Cell::Empty;
+                self.moves_without_removal = 0;
+            },
+            None => self.moves_without_removal += 1,
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let player = self.player_to_move();
+        let opponent = Morris::opponent(player);
+
+        if self.on_board(player) + self.in_hand[player.id - 1] < 3 || self.steps(player).is_empty()
+        {
+            return GameResult::Win(opponent);
+        }
+
+        if self.moves_without_removal >= MAX_MOVES_WITHOUT_REMOVAL
+        {
+            return GameResult::Draw;
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Morris
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        let p = |i: usize| self.points[i];
+
+        writeln!(f, "Board: ")?;
+        writeln!(f, "{}-----{}-----{}", p(0), p(1), p(2))?;
+        writeln!(f, "| {}---{}---{} |", p(3), p(4), p(5))?;
+        writeln!(f, "| | {}-{}-{} | |", p(6), p(7), p(8))?;
+        writeln!(
+            f,
+            "{}-{}-{}   {}-{}-{}",
+            p(9),
+            p(10),
+            p(11),
+            p(12),
+            p(13),
+            p(14)
+        )?;
+        writeln!(f, "| | {}-{}-{} | |", p(15), p(16), p(17))?;
+        writeln!(f, "| {}---{}---{} |", p(18), p(19), p(20))?;
+        writeln!(f, "{}-----{}-----{}", p(21), p(22), p(23))?;
+        writeln!(f, "In Hand: {}, {}", self.in_hand[0], self.in_hand[1])?;
+
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct MorrisMove
+{
+    // None while placing pieces
+    from: Option<usize>,
+    to: usize,
+    // the opponent's piece to remove when the move forms a mill
+    remove: Option<usize>,
+    player: Player,
+}
+
+impl Display for MorrisMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self.from
+        {
+            Some(from) => write!(f, "{}, Move: {} -> {}", self.player, from, self.to)?,
+            None => write!(f, "{}, Place: {}", self.player, self.to)?,
+        }
+        if let Some(remove) = self.remove
+        {
+            write!(f, ", Remove: {remove}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{common::board::Cell, GameResult, GameState, Player},
+        players::random::RandomPlayer,
+    };
+
+    use super::{Morris, MorrisMove, MILLS};
+
+    fn place(game: Morris, to: usize) -> Morris
+    {
+        let player = game.player_to_move();
+        game.do_move(MorrisMove {
+            from: None,
+            to,
+            remove: None,
+            player,
+        })
+    }
+
+    // a game where every piece has been placed, and player 1 can form the 0, 9, 21 mill by moving
+    // 22 to 21
+    fn moving_game() -> Morris
+    {
+        let mut game = Morris::new();
+        game.in_hand = [0, 0];
+        for point in [0, 1, 9, 22]
+        {
+            game.points[point] = Cell::Piece(Player::new(1));
+        }
+        for point in [3, 4, 18, 23]
+        {
+            game.points[point] = Cell::Piece(Player::new(2));
+        }
+        game
+    }
+
+    fn step(game: Morris, from: usize, to: usize, remove: Option<usize>) -> Morris
+    {
+        let player = game.player_to_move();
+        game.do_move(MorrisMove {
+            from: Some(from),
+            to,
+            remove,
+            player,
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_removal_without_mill()
+    {
+        step(moving_game(), 9, 10, Some(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_removing_own_piece()
+    {
+        step(moving_game(), 22, 21, Some(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mill_without_removal()
+    {
+        step(moving_game(), 22, 21, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_moving_too_far()
+    {
+        step(moving_game(), 22, 2, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_moving_twice()
+    {
+        let game = step(moving_game(), 9, 10, None);
+        game.do_move(MorrisMove {
+            from: Some(10),
+            to: 11,
+            remove: None,
+            player: Player::new(1),
+        });
+    }
+
+    #[test]
+    fn test_board()
+    {
+        // every point is in exactly two mills
+        for point in 0..24
+        {
+            assert_eq!(MILLS.iter().filter(|mill| mill.contains(&point)).count(), 2);
+        }
+        assert_eq!(Morris::new().get_valid_moves().len(), 24);
+    }
+
+    #[test]
+    fn test_mill_removes_piece()
+    {
+        let mut game = Morris::new();
+        game.in_hand = [6, 6];
+        for point in [0, 1, 21]
+        {
+            game.points[point] = Cell::Piece(Player::new(1));
+        }
+        for point in [3, 4, 5]
+        {
+            game.points[point] = Cell::Piece(Player::new(2));
+        }
+
+        // player 2's pieces at 3, 4 and 5 are a mill, so completing the top row can only remove
+        // those because every one of player 2's pieces is in a mill
+        let moves: Vec<MorrisMove> = game
+            .get_valid_moves()
+            .into_iter()
+            .filter(|m| m.to == 2)
+            .collect();
+        assert_eq!(moves.len(), 3);
+
+        game = place(game, 11);
+        game = place(game, 10);
+
+        // now player 2 has a piece outside the mill, which is the only one that can be removed
+        let moves: Vec<MorrisMove> = game
+            .get_valid_moves()
+            .into_iter()
+            .filter(|m| m.to == 2)
+            .collect();
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].remove == Some(10));
+
+        let game = game.do_move(moves[0]);
+        assert!(game.points[10] == Cell::Empty);
+        assert!(game.player_to_move() == Player::new(2));
+    }
+
+    #[test]
+    fn test_moving_and_flying()
+    {
+        let mut game = Morris::new();
+        game.in_hand = [0, 0];
+        for point in [0, 4, 23]
+        {
+            game.points[point] = Cell::Piece(Player::new(1));
+        }
+        for point in [6, 7, 12, 17]
+        {
+            game.points[point] = Cell::Piece(Player::new(2));
+        }
+
+        // player 1 is down to 3 pieces, so they can fly anywhere
+        assert_eq!(game.get_valid_moves().len(), 3 * 17);
+
+        game = game.do_move(MorrisMove {
+            from: Some(0),
+            to: 1,
+            remove: None,
+            player: Player::new(1),
+        });
+
+        // player 2 has to move along the lines, and moving either 7 or 12 to 8 forms a mill with a
+        // choice of three pieces to remove
+        let moves = game.get_valid_moves();
+        assert!(moves.iter().all(|m| m.from.is_some()));
+        assert_eq!(moves.len(), 3 + 2 * 3);
+
+        // which leaves player 1 with 2 pieces
+        game = game.do_move(MorrisMove {
+            from: Some(12),
+            to: 8,
+            remove: Some(1),
+            player: Player::new(2),
+        });
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let result = Morris::new().play(
+            &mut RandomPlayer::from_seed(0),
+            &mut RandomPlayer::from_seed(1),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::grundy::{self, mex, ImpartialGame},
+    GameResult, GameState, Player,
+};
+
+/// Multi-heap Nim. Each move removes some objects from a single heap.
+///
+/// Under normal play the player who takes the last object wins, and under misère play they lose.
+/// With a subtraction set, the number of objects taken has to be one of the numbers in the set.
+#[derive(Clone)]
+pub struct Nim
+{
+    heaps: Vec<usize>,
+    misere: bool,
+    subtraction_set: Option<Vec<usize>>,
+    last_move: Option<NimMove>,
+}
+
+impl Nim
+{
+    pub fn new(heaps: Vec<usize>) -> Nim
+    {
+        Nim {
+            heaps,
+            misere: false,
+            subtraction_set: None,
+            last_move: None,
+        }
+    }
+
+    pub fn set_misere(mut self, misere: bool) -> Nim
+    {
+        self.misere = misere;
+        self
+    }
+
+    pub fn set_subtraction_set(mut self, subtraction_set: Vec<usize>) -> Nim
+    {
+        self.subtraction_set = Some(subtraction_set);
+        self
+    }
+
+    pub fn heaps(&self) -> &Vec<usize>
+    {
+        &self.heaps
+    }
+
+    /// The XOR of the Grundy values of each heap. Under normal play, the player to move can force
+    /// a win exactly when this is not 0.
+    pub fn nim_sum(&self) -> usize
+    {
+        self.heaps
+            .iter()
+            .fold(0, |sum, &heap| sum ^ self.heap_grundy_value(heap))
+    }
+
+    /// Closed form solution for whether the player to move can force a win
+    ///
+    /// Misère play with a subtraction set has no simple closed form, so that falls back to
+    /// searching with `common::grundy::is_winning`.
+    pub fn is_winning(&self) -> bool
+    {
+        if !self.misere
+        {
+            return self.nim_sum() != 0;
+        }
+
+        if self.subtraction_set.is_some()
+        {
+            return grundy::is_winning(self);
+        }
+
+        // misère play is the same as normal play until every heap has at most 1 object, and
+        // then the player to move wins if there is an even number of heaps left
+        match self.heaps.iter().all(|&heap| heap <= 1)
+        {
+            true => self.heaps.iter().sum::<usize>() % 2 == 0,
+            false => self.nim_sum() != 0,
+        }
+    }
+
+    fn heap_grundy_value(&self, heap: usize) -> usize
+    {
+        let subtraction_set = match &self.subtraction_set
+        {
+            Some(set) => set,
+            // without a subtraction set a heap of n can move to any smaller heap, so its value is n
+            None => return heap,
+        };
+
+        let mut values: Vec<usize> = vec![];
+        for n in 0..=heap
+        {
+            let reachable: HashSet<usize> = subtraction_set
+                .iter()
+                .filter(|&&take| take <= n)
+                .map(|&take| values[n - take])
+                .collect();
+            values.push(mex(&reachable));
+        }
+
+        values[heap]
+    }
+
+    fn can_take(&self, heap: usize, take: usize) -> bool
+    {
+        take >= 1
+            && take <= self.heaps[heap]
+            && match &self.subtraction_set
+            {
+                Some(set) => set.contains(&take),
+                None => true,
+            }
+    }
+}
+
+impl GameState for Nim
+{
+    type Move = NimMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        let player = self.player_to_move();
+
+        let mut moves = vec![];
+        for heap in 0..self.heaps.len()
+        {
+            for take in 1..=self.heaps[heap]
+            {
+                if self.can_take(heap, take)
+                {
+                    moves.push(NimMove { heap, take, player });
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if !self.can_take(m.heap, m.take)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.heaps[m.heap] -= m.take;
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let can_move = (0..self.heaps.len())
+            .any(|heap| (1..=self.heaps[heap]).any(|take| self.can_take(heap, take)));
+        if can_move
+        {
+            return GameResult::InProgress;
+        }
+
+        let player_to_move = self.player_to_move();
+        match self.misere
+        {
+            // the player who made the last move loses
+            true => GameResult::Win(player_to_move),
+            false => GameResult::Win(Player {
+                id: if player_to_move.id == 1 { 2 } else { 1 },
+            }),
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl ImpartialGame for Nim
+{
+    type Key = Vec<usize>;
+
+    fn position_key(&self) -> Self::Key
+    {
+        // the order of the heaps doesn't matter
+        let mut heaps = self.heaps.clone();
+        heaps.sort_unstable();
+        heaps
+    }
+}
+
+impl Display for Nim
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Heaps: ")?;
+        for (i, heap) in self.heaps.iter().enumerate()
+        {
+            writeln!(f, "{i}: {}", "|".repeat(*heap))?;
+        }
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct NimMove
+{
+    heap: usize,
+    take: usize,
+    player: Player,
+}
+
+impl Display for NimMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}, Heap: {}, Take: {}",
+            self.player, self.heap, self.take
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::grundy::{grundy_value, is_winning},
+            GameState,
+        },
+        players::{mcts::MCTSPlayer, minimax::MinimaxPlayer, GamePlayer},
+    };
+
+    use super::{Nim, NimMove};
+
+    // plays the move the closed form says wins, or the first move when there isn't one
+    #[derive(Clone)]
+    struct NimSumPlayer;
+
+    impl GamePlayer<Nim> for NimSumPlayer
+    {
+        fn choose_move(&mut self, nim: &Nim) -> NimMove
+        {
+            let moves = nim.get_valid_moves();
+            *moves
+                .iter()
+                .find(|&&m| nim.clone().do_move(m).nim_sum() == 0)
+                .unwrap_or(&moves[0])
+        }
+    }
+
+    // every position with 3 heaps of up to `max` objects
+    fn positions(max: usize) -> Vec<Vec<usize>>
+    {
+        let mut positions = vec![];
+        for a in 0..=max
+        {
+            for b in 0..=max
+            {
+                for c in 0..=max
+                {
+                    positions.push(vec![a, b, c]);
+                }
+            }
+        }
+        positions
+    }
+
+    #[test]
+    fn test_closed_form()
+    {
+        for heaps in positions(5)
+        {
+            let nim = Nim::new(heaps.clone());
+            assert_eq!(grundy_value(&nim), nim.nim_sum(), "{heaps:?}");
+
+            let subtraction = Nim::new(heaps.clone()).set_subtraction_set(vec![1, 3, 4]);
+            assert_eq!(
+                grundy_value(&subtraction),
+                subtraction.nim_sum(),
+                "{heaps:?}"
+            );
+
+            let misere = Nim::new(heaps.clone()).set_misere(true);
+            assert_eq!(is_winning(&misere), misere.is_winning(), "{heaps:?}");
+        }
+    }
+
+    #[test]
+    fn test_minimax()
+    {
+        for heaps in positions(3)
+        {
+            for misere in [false, true]
+            {
+                let nim = Nim::new(heaps.clone()).set_misere(misere);
+                if nim.get_valid_moves().is_empty() || !nim.is_winning()
+                {
+                    continue;
+                }
+
+                // from a winning position, minimax has to leave the opponent in a losing one
+                let m = MinimaxPlayer::new(None).choose_move(&nim);
+                assert!(!nim.do_move(m).is_winning(), "{heaps:?}, misère: {misere}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mcts()
+    {
+        // reducing the heap of 4 to 3 is the only winning move
+        let nim = Nim::new(vec![1, 2, 4]);
+        assert!(nim.is_winning());
+
+        let m = MCTSPlayer::from_seed(2000, 0).choose_move(&nim);
+        assert!(!nim.do_move(m).is_winning());
+    }
+
+    #[test]
+    fn test_mcts_large_heaps()
+    {
+        // taking 5 from the heap of 13 is the only one of the 139 moves that leaves a nim-sum of
+        // 0. Random playouts are close to a coin flip after every one of them, so the playouts use
+        // the closed form and the search has to find the one move that wins them.
+        let nim = Nim::new(vec![13, 27, 41, 58]);
+        assert_eq!(nim.get_valid_moves().len(), 139);
+
+        let mut player = MCTSPlayer::from_seed(1000, 0).set_player(NimSumPlayer);
+        let m = player.choose_move(&nim);
+        assert_eq!(nim.do_move(m).nim_sum(), 0, "{m}");
+    }
+
+    #[test]
+    fn test_large_heaps()
+    {
+        // far too many moves to list as a tree, but few enough positions for the cached search
+        for heaps in [
+            vec![10, 20, 30],
+            vec![11, 20, 30],
+            vec![13, 27, 22],
+            vec![1, 1, 31],
+        ]
+        {
+            for misere in [false, true]
+            {
+                let nim = Nim::new(heaps.clone()).set_misere(misere);
+                assert_eq!(
+                    is_winning(&nim),
+                    nim.is_winning(),
+                    "{heaps:?}, misère: {misere}"
+                );
+            }
+
+            let subtraction = Nim::new(heaps.clone()).set_subtraction_set(vec![1, 3, 4]);
+            assert_eq!(
+                grundy_value(&subtraction),
+                subtraction.nim_sum(),
+                "{heaps:?}"
+            );
+        }
+
+        // 10 ^ 20 ^ 30 is 0
+        assert!(!Nim::new(vec![10, 20, 30]).is_winning());
+        assert!(Nim::new(vec![11, 20, 30]).is_winning());
+    }
+
+    #[test]
+    fn test_misere_subtraction()
+    {
+        // taking 1 or 2 at a time, whoever has to take the last object loses
+        for (heap, winning) in [(1, false), (2, true), (3, true), (4, false), (7, false)]
+        {
+            let nim = Nim::new(vec![heap])
+                .set_misere(true)
+                .set_subtraction_set(vec![1, 2]);
+            assert_eq!(nim.is_winning(), winning, "{heap}");
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Cell, Position},
+        generate_line,
+    },
+    GameResult, GameState, Player,
+};
+
+/// Pentago. The 6x6 board is split into four 3x3 quadrants, and every move places a marble and
+/// then rotates one of the quadrants a quarter turn.
+///
+/// Five in a row after the rotation wins. If the rotation gives both players five in a row at the
+/// same time the game is a draw, as is filling the board without anyone winning.
+#[derive(Clone)]
+pub struct Pentago
+{
+    board: Board<Cell>,
+    last_move: Option<PentagoMove>,
+}
+
+impl Pentago
+{
+    pub fn new() -> Pentago
+    {
+        Pentago {
+            board: Board::new(6, 6),
+            last_move: None,
+        }
+    }
+
+    fn open_positions(&self) -> Vec<Position>
+    {
+        (0..6)
+            .flat_map(|row| (0..6).map(move |col| Position { row, col }))
+            .filter(|&pos| self.board[pos] == Cell::Empty)
+            .collect()
+    }
+
+    fn rotate(&mut self, quadrant: usize, rotation: Rotation)
+    {
+        let row_offset = 3 * (quadrant / 2);
+        let col_offset = 3 * (quadrant % 2);
+        let at = |row: usize, col: usize| Position {
+            row: row_offset + row,
+            col: col_offset + col,
+        };
+
+        let old = self.board.clone();
+        for row in 0..3
+        {
+            for col in 0..3
+            {
+                self.board[at(row, col)] = match rotation
+                {
+                    Rotation::Clockwise => old[at(2 - col, row)],
+                    Rotation::Counterclockwise => old[at(col, 2 - row)],
+                };
+            }
+        }
+    }
+
+    // rotating moves marbles all over the quadrant, so every line on the board has to be checked
+    fn has_five(&self, player: Player) -> bool
+    {
+        for pos in (0..6).flat_map(|row| (0..6).map(move |col| Position { row, col }))
+        {
+            if self.board[pos] != Cell::Piece(player)
+            {
+                continue;
+            }
+
+            for dir in [(-1, -1), (-1, 0), (-1, 1), (0, 1)]
+            {
+                let line = generate_line(pos, dir, (self.board.rows(), self.board.cols()));
+                let mut consecutive = 0;
+                for p in line
+                {
+                    match self.board[p] == Cell::Piece(player)
+                    {
+                        true => consecutive += 1,
+                        false => consecutive = 0,
+                    }
+
+                    if consecutive >= 5
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl Default for Pentago
+{
+    fn default() -> Self
+    {
+        Pentago::new()
+    }
+}
+
+impl GameState for Pentago
+{
+    type Move = PentagoMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let player = self.player_to_move();
+        let mut moves = vec![];
+        for position in self.open_positions()
+        {
+            for quadrant in 0..4
+            {
+                for rotation in [Rotation::Clockwise, Rotation::Counterclockwise]
+                {
+                    moves.push(PentagoMove {
+                        position,
+                        quadrant,
+                        rotation,
+                        player,
+                    });
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if self.board[m.position] != Cell::Empty
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board[m.position] = Cell::Piece(m.player);
+        self.rotate(m.quadrant, m.rotation);
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let p1 = self.has_five(Player { id: 1 });
+        let p2 = self.has_five(Player { id: 2 });
+
+        match (p1, p2)
+        {
+            (true, true) => GameResult::Draw,
+            (true, false) => GameResult::Win(Player { id: 1 }),
+            (false, true) => GameResult::Win(Player { id: 2 }),
+            (false, false) if self.open_positions().is_empty() => GameResult::Draw,
+            (false, false) => GameResult::InProgress,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Pentago
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Rotation
+{
+    Clockwise,
+    Counterclockwise,
+}
+
+impl Display for Rotation
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Rotation::Clockwise => write!(f, "Clockwise"),
+            Rotation::Counterclockwise => write!(f, "Counterclockwise"),
+        }
+    }
+}
+
+/// Places a marble and then rotates a quadrant. Quadrants are numbered 0 to 3, left to right and
+/// then top to bottom.
+#[derive(Clone, Copy)]
+pub struct PentagoMove
+{
+    position: Position,
+    quadrant: usize,
+    rotation: Rotation,
+    player: Player,
+}
+
+impl Display for PentagoMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}, Position: {}, Rotate Quadrant {} {}",
+            self.player, self.position, self.quadrant, self.rotation
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::{
+                board::{Cell, Position},
+                create_game_tree,
+            },
+            GameResult, GameState, Player,
+        },
+        players::{mcts::MCTSPlayer, random::RandomPlayer, GamePlayer},
+    };
+
+    use super::{Pentago, PentagoMove, Rotation};
+
+    #[test]
+    fn test_rotation()
+    {
+        let game = Pentago::new().do_move(PentagoMove {
+            position: Position { row: 0, col: 0 },
+            quadrant: 0,
+            rotation: Rotation::Clockwise,
+            player: Player::new(1),
+        });
+        assert!(game.board[Position { row: 0, col: 2 }] == Cell::Piece(Player::new(1)));
+
+        let game = game.do_move(PentagoMove {
+            position: Position { row: 5, col: 5 },
+            quadrant: 0,
+            rotation: Rotation::Counterclockwise,
+            player: Player::new(2),
+        });
+        assert!(game.board[Position { row: 0, col: 0 }] == Cell::Piece(Player::new(1)));
+    }
+
+    #[test]
+    fn test_simultaneous_five()
+    {
+        let p1 = Cell::Piece(Player::new(1));
+        let p2 = Cell::Piece(Player::new(2));
+
+        // rows 0 and 2 are three in a row on the left, and the pieces on the right are in the
+        // first and last columns of the top right quadrant
+        let mut game = Pentago::new();
+        for col in 0..3
+        {
+            game.board[Position { row: 0, col }] = p1;
+            game.board[Position { row: 2, col }] = p2;
+        }
+        game.board[Position { row: 2, col: 3 }] = p1;
+        game.board[Position { row: 1, col: 5 }] = p2;
+        game.board[Position { row: 2, col: 5 }] = p2;
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // rotating clockwise turns those columns into the start of the top and bottom rows of
+        // the quadrant, completing five in a row for both players
+        let game = game.do_move(PentagoMove {
+            position: Position { row: 1, col: 3 },
+            quadrant: 1,
+            rotation: Rotation::Clockwise,
+            player: Player::new(1),
+        });
+        assert!(game.board[Position { row: 0, col: 4 }] == p1);
+        assert!(game.board[Position { row: 2, col: 4 }] == p2);
+        assert!(game.check_win() == GameResult::Draw);
+    }
+
+    #[test]
+    fn test_compound_moves()
+    {
+        let game = Pentago::new();
+        assert_eq!(game.get_valid_moves().len(), 36 * 8);
+        assert_eq!(create_game_tree(&game, Some(1)).node_count(), 1 + 36 * 8);
+
+        let result = game.play(
+            &mut RandomPlayer::from_seed(1),
+            &mut RandomPlayer::from_seed(2),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_mcts_finds_win()
+    {
+        // player 1 has four in a row along row 3, which rotating either of the top quadrants
+        // leaves alone
+        let rows = [
+            [2, 2, 1, 0, 1, 2],
+            [1, 2, 1, 2, 2, 2],
+            [2, 1, 0, 1, 2, 1],
+            [1, 1, 1, 1, 0, 0],
+            [0, 2, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0],
+        ];
+        let mut game = Pentago::new();
+        for (row, ids) in rows.iter().enumerate()
+        {
+            for (col, &id) in ids.iter().enumerate()
+            {
+                if id != 0
+                {
+                    game.board[Position { row, col }] = Cell::Piece(Player::new(id));
+                }
+            }
+        }
+        assert_eq!(game.get_valid_moves().len(), 15 * 8);
+
+        // only 4 of the moves win straight away
+        let wins =
+            |m: PentagoMove| game.clone().do_move(m).check_win() == GameResult::Win(Player::new(1));
+        assert_eq!(
+            game.get_valid_moves()
+                .into_iter()
+                .filter(|&m| wins(m))
+                .count(),
+            4
+        );
+
+        let m = MCTSPlayer::from_seed(1000, 0).choose_move(&game);
+        assert!(wins(m), "{m}");
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    games::{
+        common::{
+            board::{Board, Cell, Position},
+            Topology,
+        },
+        tictactoe::{TicTacToe, TicTacToeMove},
+        GameResult, GameState, Player,
+    },
+    players::GamePlayer,
+};
+
+/// Tic-tac-toe where neither player can see the other's stones.
+///
+/// Each player only knows about their own stones and any of their opponent's that have been
+/// revealed to them. Trying to play on a cell the opponent already has reveals that stone, and the
+/// player gets to try again. Players are only ever given a `PhantomTicTacToeView`, and `Display`
+/// only prints what the player to move knows.
+#[derive(Clone)]
+pub struct PhantomTicTacToe
+{
+    game: TicTacToe,
+    // what each player knows about the board
+    views: [TicTacToe; 2],
+    to_move: Player,
+}
+
+impl PhantomTicTacToe
+{
+    pub fn new(rows: usize, cols: usize, num_to_win: usize) -> PhantomTicTacToe
+    {
+        let game = TicTacToe::new(rows, cols, num_to_win);
+        PhantomTicTacToe {
+            views: [game.clone(), game.clone()],
+            game,
+            to_move: Player { id: 1 },
+        }
+    }
+
+    pub fn set_topology(mut self, topology: Topology) -> PhantomTicTacToe
+    {
+        self.game = self.game.set_topology(topology);
+        self.views = self.views.map(|view| view.set_topology(topology));
+        self
+    }
+
+    /// The board as `player` sees it, with their own stones and the stones they have found
+    pub fn observation(&self, player: Player) -> String
+    {
+        format!("{}", self.known(player))
+    }
+
+    /// Everything `player` is allowed to know, which is what they choose their move from
+    pub fn view(&self, player: Player) -> PhantomTicTacToeView
+    {
+        PhantomTicTacToeView {
+            game: self.views[player.id - 1].clone(),
+            to_move: player,
+            last_move: None,
+        }
+    }
+
+    fn known(&self, player: Player) -> &Board<Cell>
+    {
+        self.views[player.id - 1].board()
+    }
+
+    pub fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    pub fn do_move(mut self, m: PhantomTicTacToeMove) -> Self
+    {
+        if m.player != self.to_move || self.known(m.player)[m.position] != Cell::Empty
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        let owner = match self.game.board()[m.position]
+        {
+            // the opponent's stone is revealed and the player tries again
+            Cell::Piece(opponent) => opponent,
+            Cell::Empty =>
+            {
+                self.game = self.game.do_move(TicTacToeMove {
+                    position: m.position,
+                    player: m.player,
+                });
+                self.to_move = match m.player.id
+                {
+                    1 => Player { id: 2 },
+                    _ => Player { id: 1 },
+                };
+                m.player
+            },
+        };
+
+        let view = &mut self.views[m.player.id - 1];
+        *view = view.clone().do_move(TicTacToeMove {
+            position: m.position,
+            player: owner,
+        });
+
+        self
+    }
+
+    pub fn check_win(&self) -> GameResult
+    {
+        self.game.check_win()
+    }
+
+    /// Like `GameState::play`, except each player is only shown their own view of the board
+    pub fn play(
+        mut self,
+        p1: &mut impl GamePlayer<PhantomTicTacToeView>,
+        p2: &mut impl GamePlayer<PhantomTicTacToeView>,
+        verbose: bool,
+    ) -> GameResult
+    {
+        while self.check_win() == GameResult::InProgress
+        {
+            if verbose
+            {
+                println!("{}", self);
+            }
+
+            let view = self.view(self.to_move);
+            let selected_move = match self.to_move.id
+            {
+                1 => p1.choose_move(&view),
+                _ => p2.choose_move(&view),
+            };
+
+            if verbose
+            {
+                println!("Selected Move: {}", &selected_move);
+            }
+
+            self = self.do_move(selected_move);
+        }
+
+        let game_result = self.check_win();
+        if verbose
+        {
+            println!("{game_result}");
+        }
+
+        game_result
+    }
+}
+
+impl Display for PhantomTicTacToe
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.known(self.to_move))?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.to_move)?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+/// One player's view of `PhantomTicTacToe`. The moves are the cells they could try, and searching
+/// from here plays ordinary tic-tac-toe on the cells they know about.
+#[derive(Clone)]
+pub struct PhantomTicTacToeView
+{
+    // the stones the player knows about, as a game of tic-tac-toe
+    game: TicTacToe,
+    // the view's own turn order, since the player may know about fewer stones than they placed
+    to_move: Player,
+    last_move: Option<PhantomTicTacToeMove>,
+}
+
+impl PhantomTicTacToeView
+{
+    fn unknown_cells(&self) -> Vec<Position>
+    {
+        let board = self.game.board();
+        let mut cells = vec![];
+        for row in 0..board.rows()
+        {
+            for col in 0..board.cols()
+            {
+                let position = Position { row, col };
+                if board[position] == Cell::Empty
+                {
+                    cells.push(position);
+                }
+            }
+        }
+
+        cells
+    }
+}
+
+impl GameState for PhantomTicTacToeView
+{
+    type Move = PhantomTicTacToeMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        // any cell the player doesn't know to be taken could be played
+        self.unknown_cells()
+            .into_iter()
+            .map(|position| PhantomTicTacToeMove {
+                position,
+                player: self.to_move,
+            })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        self.to_move
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if m.player != self.to_move || self.game.board()[m.position] != Cell::Empty
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.game = self.game.do_move(TicTacToeMove {
+            position: m.position,
+            player: m.player,
+        });
+        self.to_move = match m.player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        };
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        // nothing has been played in a new view yet, and the game can't be over or there would
+        // be no view to give
+        match self.last_move
+        {
+            Some(_) => self.game.check_win(),
+            None => GameResult::InProgress,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for PhantomTicTacToeView
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.game.board())?;
+        writeln!(f, "Next Player: {}", self.to_move)
+    }
+}
+
+/// An attempt to play at a position, which only places a stone if the cell is really empty
+#[derive(Clone, Copy)]
+pub struct PhantomTicTacToeMove
+{
+    position: Position,
+    player: Player,
+}
+
+impl Display for PhantomTicTacToeMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Position: {}", self.player, self.position)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{
+            common::{board::Position, Topology},
+            GameResult, GameState, Player,
+        },
+        players::random::RandomPlayer,
+    };
+
+    use super::{PhantomTicTacToe, PhantomTicTacToeMove};
+
+    fn do_move(game: PhantomTicTacToe, row: usize, col: usize) -> PhantomTicTacToe
+    {
+        let player = game.player_to_move();
+        game.do_move(PhantomTicTacToeMove {
+            position: Position { row, col },
+            player,
+        })
+    }
+
+    // the cells the player to move could try
+    fn moves(game: &PhantomTicTacToe) -> Vec<String>
+    {
+        let view = game.view(game.player_to_move());
+        view.get_valid_moves()
+            .iter()
+            .map(|m| m.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_reveal_and_retry()
+    {
+        let game = do_move(PhantomTicTacToe::new(3, 3, 3), 1, 1);
+
+        // player 2 can't see the stone in the middle, so they can try to play there
+        assert_eq!(game.observation(Player::new(1)), "- - - \n- 1 - \n- - - \n");
+        assert_eq!(game.observation(Player::new(2)), "- - - \n- - - \n- - - \n");
+        assert_eq!(moves(&game).len(), 9);
+
+        // which reveals it, and it's still their turn
+        let game = do_move(game, 1, 1);
+        assert!(game.player_to_move() == Player::new(2));
+        assert_eq!(game.observation(Player::new(2)), "- - - \n- 1 - \n- - - \n");
+        assert_eq!(moves(&game).len(), 8);
+
+        // player 1 still doesn't know where player 2 played
+        let game = do_move(game, 0, 0);
+        assert!(game.player_to_move() == Player::new(1));
+        assert_eq!(game.observation(Player::new(1)), "- - - \n- 1 - \n- - - \n");
+        assert_eq!(moves(&game).len(), 8);
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        for seed in 0..10
+        {
+            let result = PhantomTicTacToe::new(3, 3, 3).play(
+                &mut RandomPlayer::from_seed(seed),
+                &mut RandomPlayer::from_seed(seed + 100),
+                false,
+            );
+            assert!(result != GameResult::InProgress);
+        }
+    }
+
+    #[test]
+    fn test_hidden_stones()
+    {
+        // player 1's first stone is somewhere different in each game, but player 2 is given
+        // exactly the same thing in both
+        let first = do_move(PhantomTicTacToe::new(3, 3, 3), 0, 0);
+        let second = do_move(PhantomTicTacToe::new(3, 3, 3), 2, 2);
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(moves(&first), moves(&second));
+
+        let view = first.view(Player::new(2));
+        assert_eq!(view.to_string(), second.view(Player::new(2)).to_string());
+        assert!(!view.to_string().contains('1'));
+
+        // searching from the view can't find the stone either, since playing on it works
+        let view = view.do_move(PhantomTicTacToeMove {
+            position: Position { row: 0, col: 0 },
+            player: Player::new(2),
+        });
+        assert!(view.check_win() == GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_view_topology()
+    {
+        // the broken diagonal (0, 1), (1, 2), (2, 0) only connects on a torus, and searching from
+        // player 1's view should see that too
+        for (topology, wins) in [(Topology::Flat, false), (Topology::Torus, true)]
+        {
+            let mut game = PhantomTicTacToe::new(3, 3, 3).set_topology(topology);
+            for (row, col) in [(0, 1), (0, 0), (1, 2), (1, 1)]
+            {
+                game = do_move(game, row, col);
+            }
+
+            let view = game.view(Player::new(1)).do_move(PhantomTicTacToeMove {
+                position: Position { row: 2, col: 0 },
+                player: Player::new(1),
+            });
+            assert_eq!(view.check_win() == GameResult::Win(Player::new(1)), wins);
+
+            let game = do_move(game, 2, 0);
+            assert_eq!(game.check_win() == GameResult::Win(Player::new(1)), wins);
+        }
+    }
+}
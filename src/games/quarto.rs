@@ -0,0 +1,356 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Position},
+        generate_line,
+    },
+    GameResult, GameState, Player,
+};
+
+/// Quarto. There are 16 pieces, one for every combination of four attributes, and they are shared
+/// by both players.
+///
+/// Each move places the piece your opponent chose for you and then chooses which piece your
+/// opponent has to place next. The first move of the game only chooses a piece. Whoever completes
+/// a line of four pieces that all share an attribute wins.
+#[derive(Clone)]
+pub struct Quarto
+{
+    board: Board<QuartoCell>,
+    // pieces that haven't been placed or handed to a player yet
+    available: Vec<QuartoPiece>,
+    // the piece the player to move has to place
+    in_hand: Option<QuartoPiece>,
+    last_move: Option<QuartoMove>,
+}
+
+impl Quarto
+{
+    pub fn new() -> Quarto
+    {
+        Quarto {
+            board: Board::new(4, 4),
+            available: (0..16).map(QuartoPiece).collect(),
+            in_hand: None,
+            last_move: None,
+        }
+    }
+
+    fn open_positions(&self) -> Vec<Position>
+    {
+        (0..4)
+            .flat_map(|row| (0..4).map(move |col| Position { row, col }))
+            .filter(|&pos| self.board[pos] == QuartoCell::Empty)
+            .collect()
+    }
+
+    fn completes_line(board: &Board<QuartoCell>, pos: Position) -> bool
+    {
+        for dir in [(-1, -1), (-1, 0), (-1, 1), (0, 1)]
+        {
+            let line = generate_line(pos, dir, (board.rows(), board.cols()));
+            if line.len() < 4
+            {
+                continue;
+            }
+
+            let pieces: Vec<QuartoPiece> = line
+                .iter()
+                .filter_map(|&p| match board[p]
+                {
+                    QuartoCell::Piece(piece) => Some(piece),
+                    QuartoCell::Empty => None,
+                })
+                .collect();
+            if pieces.len() < 4
+            {
+                continue;
+            }
+
+            // attributes every piece has, and attributes no piece has
+            let all = pieces.iter().fold(0b1111, |acc, piece| acc & piece.0);
+            let none = pieces.iter().fold(0b1111, |acc, piece| acc & !piece.0);
+            if all != 0 || none != 0
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl Default for Quarto
+{
+    fn default() -> Self
+    {
+        Quarto::new()
+    }
+}
+
+impl GameState for Quarto
+{
+    type Move = QuartoMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let player = self.player_to_move();
+
+        let piece = match self.in_hand
+        {
+            Some(piece) => piece,
+            // the first move only hands a piece to the opponent
+            None =>
+            {
+                return self
+                    .available
+                    .iter()
+                    .map(|&give| QuartoMove {
+                        position: None,
+                        give: Some(give),
+                        player,
+                    })
+                    .collect()
+            },
+        };
+
+        let mut moves = vec![];
+        for position in self.open_positions()
+        {
+            let mut board = self.board.clone();
+            board[position] = QuartoCell::Piece(piece);
+
+            // there is nothing left to choose once the game is won or every piece is used
+            if Quarto::completes_line(&board, position) || self.available.is_empty()
+            {
+                moves.push(QuartoMove {
+                    position: Some(position),
+                    give: None,
+                    player,
+                });
+                continue;
+            }
+
+            for &give in &self.available
+            {
+                moves.push(QuartoMove {
+                    position: Some(position),
+                    give: Some(give),
+                    player,
+                });
+            }
+        }
+
+        moves
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if let Some(position) = m.position
+        {
+            let piece = match (self.in_hand, self.board[position])
+            {
+                (Some(piece), QuartoCell::Empty) => piece,
+                _ => panic!("the move: {m} was not a valid move"),
+            };
+            self.board[position] = QuartoCell::Piece(piece);
+        }
+
+        self.in_hand = m.give;
+        if let Some(give) = m.give
+        {
+            let index = self
+                .available
+                .iter()
+                .position(|&p| p == give)
+                .expect("couldn't find piece");
+            self.available.remove(index);
+        }
+
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let last_move = match self.last_move
+        {
+            Some(m) => m,
+            None => return GameResult::InProgress,
+        };
+
+        if let Some(position) = last_move.position
+        {
+            if Quarto::completes_line(&self.board, position)
+            {
+                return GameResult::Win(last_move.player);
+            }
+        }
+
+        if self.open_positions().is_empty()
+        {
+            return GameResult::Draw;
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Quarto
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+            if let Some(piece) = self.in_hand
+            {
+                writeln!(f, "Piece to Place: {piece}")?;
+            }
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+/// One of the 16 pieces, with each of the four bits being one attribute (tall, dark, square, hollow)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct QuartoPiece(u8);
+
+impl Display for QuartoPiece
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{:04b}", self.0)
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuartoCell
+{
+    #[default]
+    Empty,
+    Piece(QuartoPiece),
+}
+
+impl Display for QuartoCell
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            QuartoCell::Empty => write!(f, "----"),
+            QuartoCell::Piece(piece) => write!(f, "{piece}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct QuartoMove
+{
+    // where to place the piece in hand, which is None for the first move of the game
+    position: Option<Position>,
+    // the piece for the opponent to place, which is None once there is nothing left to choose
+    give: Option<QuartoPiece>,
+    player: Player,
+}
+
+impl Display for QuartoMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.player)?;
+        if let Some(position) = self.position
+        {
+            write!(f, ", Position: {position}")?;
+        }
+        if let Some(give) = self.give
+        {
+            write!(f, ", Give: {give}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{common::board::Position, GameResult, GameState, Player};
+
+    use super::{Quarto, QuartoMove, QuartoPiece};
+
+    fn do_move(game: Quarto, position: Option<(usize, usize)>, give: Option<u8>) -> Quarto
+    {
+        let player = game.player_to_move();
+        game.do_move(QuartoMove {
+            position: position.map(|(row, col)| Position { row, col }),
+            give: give.map(QuartoPiece),
+            player,
+        })
+    }
+
+    #[test]
+    fn test_shared_attribute()
+    {
+        let mut game = Quarto::new();
+        assert_eq!(game.get_valid_moves().len(), 16);
+
+        game = do_move(game, None, Some(0b0001));
+        assert_eq!(game.get_valid_moves().len(), 16 * 15);
+
+        // the pieces along the top row all have the lowest bit set
+        game = do_move(game, Some((0, 0)), Some(0b0011));
+        game = do_move(game, Some((0, 1)), Some(0b0101));
+        game = do_move(game, Some((0, 2)), Some(0b1000));
+        game = do_move(game, Some((1, 0)), Some(0b1001));
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // placing the last one wins for player 2 and doesn't hand over another piece
+        let winning: Vec<QuartoMove> = game
+            .get_valid_moves()
+            .into_iter()
+            .filter(|m| m.position == Some(Position { row: 0, col: 3 }))
+            .collect();
+        assert_eq!(winning.len(), 1);
+        assert!(winning[0].give.is_none());
+
+        let game = game.do_move(winning[0]);
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+    }
+}
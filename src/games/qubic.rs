@@ -0,0 +1,256 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::Cell,
+        board3d::{self, Board3D, Position3D, DIRECTIONS},
+    },
+    GameResult, GameState, Player,
+};
+
+/// k in a row on an n x n x n cube. Qubic is the 4x4x4 game with 4 in a row, which has 76
+/// winning lines.
+///
+/// Lines can go along any axis, diagonally across any plane, or diagonally through the cube.
+#[derive(Clone)]
+pub struct Qubic
+{
+    board: Board3D<Cell>,
+    num_to_win: usize,
+    last_move: Option<QubicMove>,
+}
+
+impl Qubic
+{
+    pub fn new(size: usize, num_to_win: usize) -> Qubic
+    {
+        Qubic {
+            board: Board3D::new(size, size, size),
+            num_to_win,
+            last_move: None,
+        }
+    }
+
+    /// Every set of `num_to_win` positions in a row
+    pub fn winning_lines(&self) -> Vec<Vec<Position3D>>
+    {
+        let size = self.board.size();
+        let steps = self.num_to_win as i128 - 1;
+
+        let mut lines = vec![];
+        for start in self.board.positions()
+        {
+            for dir in DIRECTIONS
+            {
+                let start = (start.layer as i128, start.row as i128, start.col as i128);
+                let end = (
+                    start.0 + steps * dir.0,
+                    start.1 + steps * dir.1,
+                    start.2 + steps * dir.2,
+                );
+                if !board3d::on_board(end, size)
+                {
+                    continue;
+                }
+
+                lines.push(
+                    (0..self.num_to_win as i128)
+                        .map(|i| {
+                            board3d::tuple_to_pos((
+                                start.0 + i * dir.0,
+                                start.1 + i * dir.1,
+                                start.2 + i * dir.2,
+                            ))
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        lines
+    }
+
+    fn open_positions(&self) -> impl Iterator<Item = Position3D> + '_
+    {
+        self.board
+            .positions()
+            .filter(|&pos| self.board[pos] == Cell::Empty)
+    }
+}
+
+impl GameState for Qubic
+{
+    type Move = QubicMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let player = self.player_to_move();
+        self.open_positions()
+            .map(|position| QubicMove { position, player })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if self.board[m.position] != Cell::Empty
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board[m.position] = Cell::Piece(m.player);
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let last_move = match self.last_move
+        {
+            Some(m) => m,
+            None => return GameResult::InProgress,
+        };
+
+        let player = last_move.player;
+        for dir in DIRECTIONS
+        {
+            let line = board3d::generate_line(last_move.position, dir, self.board.size());
+            let mut consecutive = 0;
+            for pos in line
+            {
+                match self.board[pos] == Cell::Piece(player)
+                {
+                    true => consecutive += 1,
+                    false => consecutive = 0,
+                }
+
+                if consecutive >= self.num_to_win
+                {
+                    return GameResult::Win(player);
+                }
+            }
+        }
+
+        if self.open_positions().next().is_none()
+        {
+            return GameResult::Draw;
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Qubic
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct QubicMove
+{
+    position: Position3D,
+    player: Player,
+}
+
+impl Display for QubicMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Position: {}", self.player, self.position)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{common::board::Cell, GameResult, GameState, Player},
+        players::random::RandomPlayer,
+    };
+
+    use super::{Qubic, QubicMove};
+
+    #[test]
+    fn test_winning_lines()
+    {
+        assert_eq!(Qubic::new(4, 4).winning_lines().len(), 76);
+        assert_eq!(Qubic::new(3, 3).winning_lines().len(), 49);
+        // shorter lines than the cube can start in two places along each direction they move in
+        assert_eq!(
+            Qubic::new(4, 3).winning_lines().len(),
+            3 * 32 + 6 * 16 + 4 * 8
+        );
+    }
+
+    #[test]
+    fn test_every_line_wins()
+    {
+        let empty = Qubic::new(4, 4);
+        for line in empty.winning_lines()
+        {
+            let mut game = empty.clone();
+            for &position in &line
+            {
+                game.board[position] = Cell::Piece(Player::new(1));
+            }
+            game.last_move = Some(QubicMove {
+                position: line[1],
+                player: Player::new(1),
+            });
+            assert!(game.check_win() == GameResult::Win(Player::new(1)));
+        }
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let result = Qubic::new(4, 4).play(
+            &mut RandomPlayer::from_seed(0),
+            &mut RandomPlayer::from_seed(1),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+}
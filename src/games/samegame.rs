@@ -0,0 +1,384 @@
+use std::fmt::{self, Display, Formatter};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::games::{
+    common::board::{Board, Position},
+    GameResult, GameState, Player,
+};
+
+/// Points for clearing every block off the board
+const CLEAR_BONUS: i64 = 1000;
+
+/// SameGame, a single player puzzle played on a grid of coloured blocks.
+///
+/// Each move removes a group of two or more connected blocks of the same colour, scoring
+/// (n - 2)^2 points for a group of n blocks. Blocks above the group fall down to fill the gap, and
+/// empty columns are closed up by moving the columns to their right over to the left. The game
+/// ends when no groups are left, with a bonus for clearing the whole board.
+#[derive(Clone)]
+pub struct SameGame
+{
+    board: Board<SameGameCell>,
+    score: i64,
+    last_move: Option<SameGameMove>,
+}
+
+impl SameGame
+{
+    /// A board filled with blocks of `colors` random colours
+    pub fn new(rows: usize, cols: usize, colors: usize, seed: u64) -> SameGame
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = Board::new(rows, cols);
+        for row in 0..rows
+        {
+            for col in 0..cols
+            {
+                board[Position { row, col }] = SameGameCell::Block(rng.gen_range(1..=colors));
+            }
+        }
+
+        SameGame {
+            board,
+            score: 0,
+            last_move: None,
+        }
+    }
+
+    /// A board with the given colours, where 0 is an empty cell
+    pub fn from_rows(rows: &[&[usize]]) -> SameGame
+    {
+        let mut board = Board::new(rows.len(), rows[0].len());
+        for (row, colors) in rows.iter().enumerate()
+        {
+            for (col, &color) in colors.iter().enumerate()
+            {
+                board[Position { row, col }] = match color
+                {
+                    0 => SameGameCell::Empty,
+                    _ => SameGameCell::Block(color),
+                };
+            }
+        }
+
+        SameGame {
+            board,
+            score: 0,
+            last_move: None,
+        }
+    }
+
+    // the block at `start` and every block of the same colour connected to it
+    fn group(&self, start: Position) -> Vec<Position>
+    {
+        let color = self.board[start];
+        if color == SameGameCell::Empty
+        {
+            return vec![];
+        }
+
+        let mut group = vec![start];
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop()
+        {
+            let mut neighbours = vec![];
+            if pos.row > 0
+            {
+                neighbours.push(Position {
+                    row: pos.row - 1,
+                    col: pos.col,
+                });
+            }
+            if pos.col > 0
+            {
+                neighbours.push(Position {
+                    row: pos.row,
+                    col: pos.col - 1,
+                });
+            }
+            if pos.row + 1 < self.board.rows()
+            {
+                neighbours.push(Position {
+                    row: pos.row + 1,
+                    col: pos.col,
+                });
+            }
+            if pos.col + 1 < self.board.cols()
+            {
+                neighbours.push(Position {
+                    row: pos.row,
+                    col: pos.col + 1,
+                });
+            }
+
+            for next in neighbours
+            {
+                if self.board[next] == color && !group.contains(&next)
+                {
+                    group.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+
+        group
+    }
+
+    // every group that can be removed, each listed once
+    fn groups(&self) -> Vec<Vec<Position>>
+    {
+        let mut seen = Board::<bool>::new(self.board.rows(), self.board.cols());
+        let mut groups = vec![];
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                let position = Position { row, col };
+                if seen[position]
+                {
+                    continue;
+                }
+
+                let group = self.group(position);
+                for &pos in &group
+                {
+                    seen[pos] = true;
+                }
+                if group.len() >= 2
+                {
+                    groups.push(group);
+                }
+            }
+        }
+
+        groups
+    }
+
+    fn is_cleared(&self) -> bool
+    {
+        (0..self.board.cols()).all(|col| {
+            self.board[Position {
+                row: self.board.rows() - 1,
+                col,
+            }] == SameGameCell::Empty
+        })
+    }
+
+    // drops blocks into the gaps below them and closes up empty columns
+    fn collapse(&mut self)
+    {
+        let (rows, cols) = (self.board.rows(), self.board.cols());
+
+        let mut columns = vec![];
+        for col in 0..cols
+        {
+            let blocks: Vec<SameGameCell> = (0..rows)
+                .rev()
+                .map(|row| self.board[Position { row, col }])
+                .filter(|&cell| cell != SameGameCell::Empty)
+                .collect();
+            if !blocks.is_empty()
+            {
+                columns.push(blocks);
+            }
+        }
+
+        self.board = Board::new(rows, cols);
+        for (col, blocks) in columns.iter().enumerate()
+        {
+            for (i, &block) in blocks.iter().enumerate()
+            {
+                self.board[Position {
+                    row: rows - 1 - i,
+                    col,
+                }] = block;
+            }
+        }
+    }
+}
+
+impl GameState for SameGame
+{
+    type Move = SameGameMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        // each group is identified by its first block in reading order
+        self.groups()
+            .iter()
+            .map(|group| SameGameMove {
+                position: group[0],
+                player: Player { id: 1 },
+            })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        Player { id: 1 }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        let group = self.group(m.position);
+        if group.len() < 2
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        for &pos in &group
+        {
+            self.board[pos] = SameGameCell::Empty;
+        }
+        self.score += (group.len() as i64 - 2).pow(2);
+        self.collapse();
+        if self.is_cleared()
+        {
+            self.score += CLEAR_BONUS;
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        match self.groups().is_empty()
+        {
+            true => GameResult::Score(self.score),
+            false => GameResult::InProgress,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for SameGame
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        writeln!(f, "Score: {}", self.score)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum SameGameCell
+{
+    #[default]
+    Empty,
+    Block(usize),
+}
+
+impl Display for SameGameCell
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            SameGameCell::Empty => write!(f, "-"),
+            SameGameCell::Block(color) => write!(f, "{color}"),
+        }
+    }
+}
+
+/// Removes the group containing `position`
+#[derive(Clone, Copy)]
+pub struct SameGameMove
+{
+    position: Position,
+    player: Player,
+}
+
+impl Display for SameGameMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Position: {}", self.player, self.position)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{common::board::Position, GameResult, GameState, Player},
+        players::{mcts::MCTSPlayer, random::RandomPlayer},
+    };
+
+    use super::{SameGame, SameGameMove};
+
+    fn remove(game: SameGame, row: usize, col: usize) -> SameGame
+    {
+        game.do_move(SameGameMove {
+            position: Position { row, col },
+            player: Player::new(1),
+        })
+    }
+
+    #[test]
+    fn test_removing_groups()
+    {
+        let game = SameGame::from_rows(&[&[1, 2, 2], &[1, 1, 3], &[2, 1, 3]]);
+        assert_eq!(game.get_valid_moves().len(), 3);
+
+        // removing the four 1s lets the blocks above them fall
+        let game = remove(game, 0, 0);
+        assert_eq!(game.score, 4);
+        assert_eq!(game.board.to_string(), "- - 2 \n- - 3 \n2 2 3 \n");
+
+        // removing the 2s along the bottom empties two columns, which close up
+        let game = remove(game, 2, 0);
+        assert_eq!(game.board.to_string(), "2 - - \n3 - - \n3 - - \n");
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // which leaves a single 2, so the game is over without the bonus
+        let game = remove(game, 1, 0);
+        assert!(game.check_win() == GameResult::Score(4));
+    }
+
+    #[test]
+    fn test_clear_bonus()
+    {
+        let game = SameGame::from_rows(&[&[1, 2], &[1, 2]]);
+        let game = remove(remove(game, 0, 0), 0, 0);
+        assert!(game.check_win() == GameResult::Score(1000));
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let result = SameGame::new(8, 8, 3, 0).play_alone(&mut RandomPlayer::from_seed(0), false);
+        assert!(matches!(result, GameResult::Score(score) if score >= 0));
+    }
+
+    #[test]
+    fn test_mcts()
+    {
+        let score = |result| match result
+        {
+            GameResult::Score(score) => score,
+            result => panic!("expected a score, not {result}"),
+        };
+
+        // the search finds a way to clear the board, which beats every random playout
+        let game = SameGame::new(6, 6, 3, 0);
+        let random = (0..20)
+            .map(|seed| {
+                score(
+                    game.clone()
+                        .play_alone(&mut RandomPlayer::from_seed(seed), false),
+                )
+            })
+            .max()
+            .unwrap();
+        let mcts = score(game.play_alone(&mut MCTSPlayer::from_seed(300, 0), false));
+        assert!(mcts >= 1000 && mcts > random, "{mcts} {random}");
+    }
+}
@@ -0,0 +1,478 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Position},
+        on_board, tuple_to_pos,
+    },
+    GameResult, GameState, Player,
+};
+
+/// The level a worker has to climb to in order to win, which is also the highest level that can
+/// be built before a dome
+const TOP_LEVEL: u8 = 3;
+
+const DIRECTIONS: [(i128, i128); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Santorini on a 5x5 board, without god powers. Each player has two workers, which start on the
+/// squares diagonally next to the centre.
+///
+/// A turn is moving one worker to a neighbouring square and then building on a square next to
+/// where it ended up. Workers can move down any number of levels but only up one, and can't move
+/// onto other workers or domes. Building adds a level to a square, and a dome on top of level 3. A
+/// worker moving up to level 3 wins straight away, without building. A player who can't move
+/// loses.
+#[derive(Clone)]
+pub struct Santorini
+{
+    board: Board<SantoriniCell>,
+    last_move: Option<SantoriniMove>,
+}
+
+impl Santorini
+{
+    pub fn new() -> Santorini
+    {
+        let mut board = Board::<SantoriniCell>::new(5, 5);
+        for (row, col, id) in [(1, 1, 1), (3, 3, 1), (1, 3, 2), (3, 1, 2)]
+        {
+            board[Position { row, col }].worker = Some(Player { id });
+        }
+
+        Santorini {
+            board,
+            last_move: None,
+        }
+    }
+
+    fn opponent(player: Player) -> Player
+    {
+        match player.id
+        {
+            1 => Player { id: 2 },
+            _ => Player { id: 1 },
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position>
+    {
+        let cols = self.board.cols();
+        (0..self.board.rows()).flat_map(move |row| (0..cols).map(move |col| Position { row, col }))
+    }
+
+    // the empty squares next to `from`, which workers can stand on and builders can build on
+    fn neighbours(board: &Board<SantoriniCell>, from: Position) -> Vec<Position>
+    {
+        let size = (board.rows(), board.cols());
+        DIRECTIONS
+            .iter()
+            .map(|dir| (from.row as i128 + dir.0, from.col as i128 + dir.1))
+            .filter(|&pos| on_board(pos, size))
+            .map(tuple_to_pos)
+            .filter(|&pos| board[pos].worker.is_none() && !board[pos].dome)
+            .collect()
+    }
+
+    fn is_adjacent(a: Position, b: Position) -> bool
+    {
+        a != b && a.row.abs_diff(b.row) <= 1 && a.col.abs_diff(b.col) <= 1
+    }
+
+    // whether a worker at `from` can step to `to`, which has to be free and at most one level up
+    fn can_step(&self, from: Position, to: Position) -> bool
+    {
+        let (rows, cols) = (self.board.rows(), self.board.cols());
+        to.row < rows
+            && to.col < cols
+            && Santorini::is_adjacent(from, to)
+            && self.board[to].worker.is_none()
+            && !self.board[to].dome
+            && self.board[to].level <= self.board[from].level + 1
+    }
+
+    // a worker that can step anywhere can always build on the square it left, so this is enough
+    // to know that `player` has a move
+    fn has_move(&self, player: Player) -> bool
+    {
+        self.positions()
+            .filter(|&from| self.board[from].worker == Some(player))
+            .any(|from| {
+                Santorini::neighbours(&self.board, from)
+                    .into_iter()
+                    .any(|to| self.can_step(from, to))
+            })
+    }
+
+    fn is_valid(&self, m: SantoriniMove) -> bool
+    {
+        let (rows, cols) = (self.board.rows(), self.board.cols());
+        if m.player != self.player_to_move()
+            || m.from.row >= rows
+            || m.from.col >= cols
+            || self.board[m.from].worker != Some(m.player)
+            || !self.can_step(m.from, m.to)
+        {
+            return false;
+        }
+
+        let climbs_to_top =
+            self.board[m.to].level == TOP_LEVEL && self.board[m.from].level < TOP_LEVEL;
+        match (climbs_to_top, m.build)
+        {
+            (true, None) => true,
+            (false, Some(build)) =>
+            {
+                // the square the worker left is free to build on
+                build.row < rows
+                    && build.col < cols
+                    && Santorini::is_adjacent(m.to, build)
+                    && (build == m.from
+                        || (self.board[build].worker.is_none() && !self.board[build].dome))
+            },
+            _ => false,
+        }
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<SantoriniMove>
+    {
+        let mut moves = vec![];
+        for from in self.positions()
+        {
+            if self.board[from].worker != Some(player)
+            {
+                continue;
+            }
+
+            let mut board = self.board.clone();
+            board[from].worker = None;
+            for to in Santorini::neighbours(&board, from)
+            {
+                let (from_level, to_level) = (board[from].level, board[to].level);
+                if to_level > from_level + 1
+                {
+                    continue;
+                }
+
+                // climbing to the top wins, so there's nothing to build
+                if to_level == TOP_LEVEL && from_level < TOP_LEVEL
+                {
+                    moves.push(SantoriniMove {
+                        from,
+                        to,
+                        build: None,
+                        player,
+                    });
+                    continue;
+                }
+
+                // the square the worker left is free to build on
+                for build in Santorini::neighbours(&board, to)
+                {
+                    moves.push(SantoriniMove {
+                        from,
+                        to,
+                        build: Some(build),
+                        player,
+                    });
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+impl Default for Santorini
+{
+    fn default() -> Self
+    {
+        Santorini::new()
+    }
+}
+
+impl GameState for Santorini
+{
+    type Move = SantoriniMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        self.moves_for(self.player_to_move())
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) => Santorini::opponent(last_move.player),
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if !self.is_valid(m)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.board[m.from].worker = None;
+        self.board[m.to].worker = Some(m.player);
+        if let Some(build) = m.build
+        {
+            let cell = &mut self.board[build];
+            match cell.level
+            {
+                TOP_LEVEL => cell.dome = true,
+                _ => cell.level += 1,
+            }
+        }
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if let Some(SantoriniMove {
+            build: None,
+            player,
+            ..
+        }) = self.last_move
+        {
+            return GameResult::Win(player);
+        }
+
+        let player_to_move = self.player_to_move();
+        if !self.has_move(player_to_move)
+        {
+            return GameResult::Win(Santorini::opponent(player_to_move));
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for Santorini
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+/// A square's building and the worker standing on it, if any
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct SantoriniCell
+{
+    level: u8,
+    dome: bool,
+    worker: Option<Player>,
+}
+
+impl Display for SantoriniCell
+{
+    /// The level, or D for a dome, followed by the player whose worker is there
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self.dome
+        {
+            true => write!(f, "D")?,
+            false => write!(f, "{}", self.level)?,
+        }
+        match self.worker
+        {
+            Some(player) => write!(f, "{}", player.id),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+/// Moves the worker at `from` to `to` and then builds at `build`, which is None for a winning move
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SantoriniMove
+{
+    from: Position,
+    to: Position,
+    build: Option<Position>,
+    player: Player,
+}
+
+impl Display for SantoriniMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, From: {}, To: {}", self.player, self.from, self.to)?;
+        if let Some(build) = self.build
+        {
+            write!(f, ", Build: {build}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{common::board::Position, GameResult, GameState, Player},
+        players::{random::RandomPlayer, GamePlayer},
+    };
+
+    use super::{Santorini, SantoriniMove};
+
+    fn empty_game() -> Santorini
+    {
+        let mut game = Santorini::new();
+        for row in 0..5
+        {
+            for col in 0..5
+            {
+                game.board[Position { row, col }].worker = None;
+            }
+        }
+        game
+    }
+
+    #[test]
+    fn test_building()
+    {
+        let mut game = empty_game();
+        game.board[Position { row: 0, col: 0 }].worker = Some(Player::new(1));
+        game.board[Position { row: 4, col: 4 }].worker = Some(Player::new(2));
+        game.board[Position { row: 0, col: 1 }].level = 2;
+        game.board[Position { row: 1, col: 1 }].level = 3;
+
+        // the worker can only move down to (1, 0), since the other squares are too high
+        let moves = game.get_valid_moves();
+        assert!(moves.iter().all(|m| m.to == Position { row: 1, col: 0 }));
+        // and can build anywhere around it, including where it came from
+        assert_eq!(moves.len(), 5);
+
+        let game = game.do_move(SantoriniMove {
+            from: Position { row: 0, col: 0 },
+            to: Position { row: 1, col: 0 },
+            build: Some(Position { row: 1, col: 1 }),
+            player: Player::new(1),
+        });
+        let cell = game.board[Position { row: 1, col: 1 }];
+        assert!(cell.dome && cell.level == 3);
+        assert_eq!(
+            game.board.to_string().lines().nth(1).unwrap(),
+            "01 D- 0- 0- 0- "
+        );
+    }
+
+    #[test]
+    fn test_climbing_wins()
+    {
+        let mut game = empty_game();
+        game.board[Position { row: 2, col: 2 }].worker = Some(Player::new(1));
+        game.board[Position { row: 2, col: 2 }].level = 2;
+        game.board[Position { row: 2, col: 3 }].level = 3;
+        game.board[Position { row: 4, col: 4 }].worker = Some(Player::new(2));
+
+        let winning: Vec<SantoriniMove> = game
+            .get_valid_moves()
+            .into_iter()
+            .filter(|m| m.build.is_none())
+            .collect();
+        assert_eq!(winning.len(), 1);
+
+        let game = game.do_move(winning[0]);
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    fn test_no_moves_loses()
+    {
+        // player 1's only worker is boxed in by domes and a tall tower
+        let mut game = empty_game();
+        game.board[Position { row: 0, col: 0 }].worker = Some(Player::new(1));
+        game.board[Position { row: 0, col: 1 }].dome = true;
+        game.board[Position { row: 1, col: 0 }].dome = true;
+        game.board[Position { row: 1, col: 1 }].level = 2;
+        game.board[Position { row: 4, col: 4 }].worker = Some(Player::new(2));
+
+        assert!(game.check_win() == GameResult::Win(Player::new(2)));
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        assert!(!Santorini::new().get_valid_moves().is_empty());
+        for seed in 0..5
+        {
+            let result = Santorini::new().play(
+                &mut RandomPlayer::from_seed(seed),
+                &mut RandomPlayer::from_seed(seed + 100),
+                false,
+            );
+            assert!(result != GameResult::Draw);
+        }
+    }
+
+    #[test]
+    fn test_single_move_check()
+    {
+        // checking one move has to agree with generating all of them
+        let mut game = Santorini::new();
+        let mut player = RandomPlayer::from_seed(0);
+        let positions: Vec<Position> = game.positions().collect();
+        while game.check_win() == GameResult::InProgress
+        {
+            let mover = game.player_to_move();
+            let valid = game.moves_for(mover);
+            for &from in positions
+                .iter()
+                .filter(|&&from| game.board[from].worker == Some(mover))
+            {
+                for &to in &positions
+                {
+                    let builds = positions.iter().map(|&build| Some(build));
+                    for build in builds.chain([None])
+                    {
+                        let m = SantoriniMove {
+                            from,
+                            to,
+                            build,
+                            player: mover,
+                        };
+                        assert_eq!(game.is_valid(m), valid.contains(&m), "{m}");
+                    }
+                }
+            }
+
+            let m = player.choose_move(&game);
+            game = game.do_move(m);
+        }
+    }
+}
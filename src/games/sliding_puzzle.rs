@@ -0,0 +1,343 @@
+use std::fmt::{self, Display, Formatter};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::games::{
+    common::board::{Board, Position},
+    GameResult, GameState, Player,
+};
+
+/// A sliding puzzle like the 15-puzzle, where tiles are slid into the gap until they are in
+/// order, with the gap in the bottom right corner.
+///
+/// The score is minus the number of moves taken, so shorter solutions are better. The puzzle stops
+/// after a move limit, and any tiles that are out of place then count against the score by how far
+/// they are from where they should be. That makes an unsolved puzzle score lower than a solved one.
+#[derive(Clone)]
+pub struct SlidingPuzzle
+{
+    // 0 is the gap
+    board: Board<usize>,
+    gap: Position,
+    moves: usize,
+    move_limit: usize,
+    last_move: Option<SlidingPuzzleMove>,
+}
+
+impl SlidingPuzzle
+{
+    /// A solved puzzle with `size` rows and columns
+    pub fn new(size: usize) -> SlidingPuzzle
+    {
+        let mut board = Board::new(size, size);
+        for row in 0..size
+        {
+            for col in 0..size
+            {
+                board[Position { row, col }] = (row * size + col + 1) % (size * size);
+            }
+        }
+
+        SlidingPuzzle {
+            board,
+            gap: Position {
+                row: size - 1,
+                col: size - 1,
+            },
+            moves: 0,
+            move_limit: 200,
+            last_move: None,
+        }
+    }
+
+    /// The 15-puzzle, mixed up with `shuffle_moves` random moves
+    pub fn fifteen(shuffle_moves: usize, seed: u64) -> SlidingPuzzle
+    {
+        SlidingPuzzle::new(4).shuffle(shuffle_moves, seed)
+    }
+
+    /// A puzzle with the tiles listed in reading order, where 0 is the gap
+    pub fn from_tiles(size: usize, tiles: &[usize]) -> SlidingPuzzle
+    {
+        let mut sorted = tiles.to_vec();
+        sorted.sort_unstable();
+        if sorted != (0..size * size).collect::<Vec<usize>>()
+        {
+            panic!("the tiles have to be the numbers 0 to {}", size * size - 1);
+        }
+
+        let mut puzzle = SlidingPuzzle::new(size);
+        for (i, &tile) in tiles.iter().enumerate()
+        {
+            let position = Position {
+                row: i / size,
+                col: i % size,
+            };
+            puzzle.board[position] = tile;
+            if tile == 0
+            {
+                puzzle.gap = position;
+            }
+        }
+
+        if !puzzle.is_solvable()
+        {
+            panic!("the puzzle can't be solved");
+        }
+        puzzle
+    }
+
+    /// Mixes up the tiles by making `moves` random moves, which keeps the puzzle solvable
+    pub fn shuffle(mut self, moves: usize, seed: u64) -> SlidingPuzzle
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..moves
+        {
+            let tile = *self.movable_tiles().choose(&mut rng).unwrap();
+            self.slide(tile);
+        }
+        self
+    }
+
+    pub fn set_move_limit(mut self, move_limit: usize) -> SlidingPuzzle
+    {
+        self.move_limit = move_limit;
+        self
+    }
+
+    /// The total distance of the tiles from where they should be, moving along rows and columns
+    pub fn distance(&self) -> usize
+    {
+        let size = self.board.rows();
+        let mut distance = 0;
+        for row in 0..size
+        {
+            for col in 0..size
+            {
+                let tile = self.board[Position { row, col }];
+                if tile != 0
+                {
+                    distance += row.abs_diff((tile - 1) / size) + col.abs_diff((tile - 1) % size);
+                }
+            }
+        }
+        distance
+    }
+
+    // a puzzle can be solved when the parity of the tiles' order matches the gap's row
+    fn is_solvable(&self) -> bool
+    {
+        let size = self.board.rows();
+        let tiles: Vec<usize> = (0..size * size)
+            .map(|i| {
+                self.board[Position {
+                    row: i / size,
+                    col: i % size,
+                }]
+            })
+            .filter(|&tile| tile != 0)
+            .collect();
+        let inversions = (0..tiles.len())
+            .flat_map(|i| (i + 1..tiles.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| tiles[i] > tiles[j])
+            .count();
+
+        match size % 2
+        {
+            1 => inversions % 2 == 0,
+            _ => (inversions + self.gap.row) % 2 == 1,
+        }
+    }
+
+    fn movable_tiles(&self) -> Vec<usize>
+    {
+        let size = self.board.rows();
+        let mut neighbours = vec![];
+        if self.gap.row > 0
+        {
+            neighbours.push(Position {
+                row: self.gap.row - 1,
+                col: self.gap.col,
+            });
+        }
+        if self.gap.col > 0
+        {
+            neighbours.push(Position {
+                row: self.gap.row,
+                col: self.gap.col - 1,
+            });
+        }
+        if self.gap.row + 1 < size
+        {
+            neighbours.push(Position {
+                row: self.gap.row + 1,
+                col: self.gap.col,
+            });
+        }
+        if self.gap.col + 1 < size
+        {
+            neighbours.push(Position {
+                row: self.gap.row,
+                col: self.gap.col + 1,
+            });
+        }
+
+        neighbours.into_iter().map(|pos| self.board[pos]).collect()
+    }
+
+    fn slide(&mut self, tile: usize)
+    {
+        let size = self.board.rows();
+        let position = (0..size * size)
+            .map(|i| Position {
+                row: i / size,
+                col: i % size,
+            })
+            .find(|&pos| self.board[pos] == tile)
+            .unwrap();
+
+        self.board[self.gap] = tile;
+        self.board[position] = 0;
+        self.gap = position;
+    }
+}
+
+impl GameState for SlidingPuzzle
+{
+    type Move = SlidingPuzzleMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        self.movable_tiles()
+            .into_iter()
+            .map(|tile| SlidingPuzzleMove {
+                tile,
+                player: Player { id: 1 },
+            })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        Player { id: 1 }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        if m.tile == 0 || !self.movable_tiles().contains(&m.tile)
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        self.slide(m.tile);
+        self.moves += 1;
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        let distance = self.distance();
+        match distance == 0 || self.moves >= self.move_limit
+        {
+            true => GameResult::Score(-((self.moves + distance) as i64)),
+            false => GameResult::InProgress,
+        }
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for SlidingPuzzle
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        let width = (self.board.rows() * self.board.cols() - 1)
+            .to_string()
+            .len();
+
+        writeln!(f, "Board: ")?;
+        for row in 0..self.board.rows()
+        {
+            for col in 0..self.board.cols()
+            {
+                match self.board[Position { row, col }]
+                {
+                    0 => write!(f, "{:>width$} ", "-")?,
+                    tile => write!(f, "{tile:>width$} ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Moves: {}", self.moves)?;
+        Ok(())
+    }
+}
+
+/// Slides `tile` into the gap
+#[derive(Clone, Copy)]
+pub struct SlidingPuzzleMove
+{
+    tile: usize,
+    player: Player,
+}
+
+impl Display for SlidingPuzzleMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Tile: {}", self.player, self.tile)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{GameResult, GameState, Player};
+
+    use super::{SlidingPuzzle, SlidingPuzzleMove};
+
+    #[test]
+    fn test_sliding()
+    {
+        let game = SlidingPuzzle::from_tiles(3, &[1, 2, 3, 4, 5, 6, 0, 7, 8]);
+        assert_eq!(game.get_valid_moves().len(), 2);
+        assert_eq!(game.distance(), 2);
+
+        let slide = |game: SlidingPuzzle, tile: usize| {
+            game.do_move(SlidingPuzzleMove {
+                tile,
+                player: Player::new(1),
+            })
+        };
+        let game = slide(game, 7);
+        assert!(game.check_win() == GameResult::InProgress);
+        let game = slide(game, 8);
+        assert!(game.check_win() == GameResult::Score(-2));
+    }
+
+    #[test]
+    fn test_move_limit()
+    {
+        let game = SlidingPuzzle::fifteen(50, 0).set_move_limit(0);
+        assert!(game.check_win() == GameResult::Score(-(game.distance() as i64)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsolvable()
+    {
+        // swapping two tiles of a solved puzzle can't be undone by sliding
+        SlidingPuzzle::from_tiles(4, &[2, 1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0]);
+    }
+}
@@ -6,16 +6,30 @@ use std::{
 use crate::games::{
     common::{
         board::{Board, Cell, Position},
-        generate_line,
+        evaluate_windows, generate_wrapped_line, Topology,
     },
     GameResult, GameState, Player,
 };
+use crate::players::minimax::Evaluator;
 
+/// An (m,n,k,p,q) game, where players take turns placing stones on an m x n board and the first
+/// to get k in a row wins. The first turn places q stones and every turn after that places p.
+///
+/// Tic-tac-toe is (3,3,3,1,1), which is what `new` creates for any board size, and Connect6 is
+/// (19,19,6,2,1). Each stone is its own move, so a turn of several stones is several moves in a
+/// row by the same player.
+///
+/// The board can also be a cylinder or torus, so that lines wrap around the edges.
 #[derive(Clone)]
 pub struct TicTacToe
 {
     board: Board<Cell>,
     num_to_win: usize,
+    // p, the number of stones placed each turn
+    stones_per_turn: usize,
+    // q, the number of stones placed on the first turn
+    first_turn_stones: usize,
+    topology: Topology,
     open_positions: Vec<Position>,
     last_move: Option<TicTacToeMove>,
 }
@@ -36,10 +50,51 @@ impl TicTacToe
         TicTacToe {
             board: Board::new(rows, cols),
             num_to_win,
+            stones_per_turn: 1,
+            first_turn_stones: 1,
+            topology: Topology::Flat,
             open_positions,
             last_move: None,
         }
     }
+
+    /// Connect6, which is (19,19,6,2,1)
+    pub fn connect6() -> TicTacToe
+    {
+        TicTacToe::new(19, 19, 6).set_stones_per_turn(2, 1)
+    }
+
+    /// Sets p, the number of stones placed each turn, and q, the number placed on the first turn
+    pub fn set_stones_per_turn(mut self, p: usize, q: usize) -> TicTacToe
+    {
+        if p == 0 || q == 0
+        {
+            panic!("every turn has to place at least one stone, not p = {p}, q = {q}");
+        }
+
+        self.stones_per_turn = p;
+        self.first_turn_stones = q;
+        self
+    }
+
+    pub fn set_topology(mut self, topology: Topology) -> TicTacToe
+    {
+        self.topology = topology;
+        self
+    }
+
+    pub fn board(&self) -> &Board<Cell>
+    {
+        &self.board
+    }
+
+    // whether the last stone placed was the last one of its turn
+    fn turn_finished(&self) -> bool
+    {
+        let placed = self.board.rows() * self.board.cols() - self.open_positions.len();
+        placed >= self.first_turn_stones
+            && (placed - self.first_turn_stones).is_multiple_of(self.stones_per_turn)
+    }
 }
 
 impl GameState for TicTacToe
@@ -67,6 +122,8 @@ impl GameState for TicTacToe
     {
         match self.last_move
         {
+            // the same player keeps placing stones until their turn is over
+            Some(last_move) if !self.turn_finished() => last_move.player,
             Some(last_move) =>
             {
                 if last_move.player.id == 1
@@ -114,7 +171,13 @@ impl GameState for TicTacToe
 
         for dir in [(-1, -1), (-1, 0), (-1, 1), (0, 1)]
         {
-            let line = generate_line(start_pos, dir, (board.rows(), board.cols()));
+            let line = generate_wrapped_line(
+                start_pos,
+                dir,
+                (board.rows(), board.cols()),
+                self.topology,
+                self.num_to_win,
+            );
             let mut consecutive = 0;
             for pos in line
             {
@@ -180,3 +243,144 @@ impl Display for TicTacToeMove
         write!(f, "{}, Position: {}", self.player, self.position)
     }
 }
+
+/// Counts the lines each player could still complete, for searches that stop before the end of
+/// the game
+#[derive(Clone, Copy, Default)]
+pub struct TicTacToeEvaluator;
+
+impl Evaluator<TicTacToe> for TicTacToeEvaluator
+{
+    fn evaluate(&self, state: &TicTacToe) -> f32
+    {
+        evaluate_windows(
+            &state.board,
+            state.topology,
+            state.num_to_win,
+            state.player_to_move(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{
+        common::{board::Position, Topology},
+        GameResult, GameState, Player,
+    };
+
+    use super::{TicTacToe, TicTacToeMove};
+
+    fn do_move(game: TicTacToe, row: usize, col: usize) -> TicTacToe
+    {
+        let player = game.player_to_move();
+        game.do_move(TicTacToeMove {
+            position: Position { row, col },
+            player,
+        })
+    }
+
+    #[test]
+    fn test_tictactoe_alternates()
+    {
+        let mut game = TicTacToe::new(3, 3, 3);
+        for (i, (row, col)) in [(1, 1), (0, 0), (2, 2), (0, 2)].into_iter().enumerate()
+        {
+            let expected = if i % 2 == 0 { 1 } else { 2 };
+            assert!(game.player_to_move() == Player::new(expected));
+            game = do_move(game, row, col);
+        }
+        assert!(game.player_to_move() == Player::new(1));
+    }
+
+    #[test]
+    fn test_torus()
+    {
+        // the broken diagonal (0, 1), (1, 2), (2, 0) only connects on a torus
+        let moves = [(0, 1), (0, 0), (1, 2), (1, 1), (2, 0)];
+        for (topology, wins) in [
+            (Topology::Flat, false),
+            (Topology::Cylinder, true),
+            (Topology::Torus, true),
+        ]
+        {
+            let mut game = TicTacToe::new(3, 3, 3).set_topology(topology);
+            for (row, col) in moves
+            {
+                game = do_move(game, row, col);
+            }
+            assert_eq!(game.check_win() == GameResult::Win(Player::new(1)), wins);
+        }
+
+        // a column only wraps around on a torus
+        let moves = [(2, 0), (1, 1), (0, 0), (1, 2), (3, 0)];
+        for (topology, wins) in [(Topology::Cylinder, false), (Topology::Torus, true)]
+        {
+            let mut game = TicTacToe::new(4, 4, 3).set_topology(topology);
+            for (row, col) in moves
+            {
+                game = do_move(game, row, col);
+            }
+            assert_eq!(game.check_win() == GameResult::Win(Player::new(1)), wins);
+        }
+    }
+
+    #[test]
+    fn test_torus_line_longer_than_board()
+    {
+        // on a 3x3 torus a row of 3 can't count as 4 in a row by wrapping around onto itself
+        let mut game = TicTacToe::new(3, 3, 4).set_topology(Topology::Torus);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]
+        {
+            game = do_move(game, row, col);
+        }
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_connect6_turns()
+    {
+        // player 1 places one stone, and then each player places two
+        let expected = [1, 2, 2, 1, 1, 2, 2, 1];
+        let mut game = TicTacToe::connect6();
+        for (col, id) in expected.into_iter().enumerate()
+        {
+            assert!(game.player_to_move() == Player::new(id));
+            game = do_move(game, 0, col);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_no_stones_per_turn()
+    {
+        TicTacToe::new(3, 3, 3).set_stones_per_turn(0, 1);
+    }
+
+    #[test]
+    fn test_connect6_win_mid_turn()
+    {
+        let mut game = TicTacToe::connect6();
+        game = do_move(game, 9, 4);
+
+        // player 1 builds a line along row 9 while player 2 plays near the top
+        for turn in 0..3
+        {
+            game = do_move(game, 0, 3 * turn);
+            game = do_move(game, 1, 3 * turn);
+            if turn < 2
+            {
+                game = do_move(game, 9, 5 + 2 * turn);
+                game = do_move(game, 9, 6 + 2 * turn);
+            }
+        }
+        assert!(game.player_to_move() == Player::new(1));
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // the first stone of player 1's turn makes six, which ends the game straight away
+        game = do_move(game, 9, 9);
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+}
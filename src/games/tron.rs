@@ -0,0 +1,288 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::{
+        board::{Board, Cell, Position},
+        on_board, tuple_to_pos,
+    },
+    GameResult, Player, SimultaneousGame,
+};
+
+/// Light cycles on a grid. Both players move one square at the same time, leaving a wall behind
+/// them.
+///
+/// A player crashes by driving off the board or into a wall. If both players crash on the same
+/// turn, including by driving into the same square, the game is a draw.
+#[derive(Clone)]
+pub struct Tron
+{
+    // walls left by each player, including the square they are on
+    board: Board<Cell>,
+    heads: [Position; 2],
+    crashed: [bool; 2],
+    last_moves: Option<(TronMove, TronMove)>,
+}
+
+impl Tron
+{
+    /// Starts the players on opposite sides of the middle row, which needs at least one row and
+    /// four columns so that they start apart
+    pub fn new(rows: usize, cols: usize) -> Tron
+    {
+        if rows < 1 || cols < 4
+        {
+            panic!("a {rows}x{cols} board is too small to play on");
+        }
+
+        let heads = [
+            Position {
+                row: rows / 2,
+                col: 1,
+            },
+            Position {
+                row: rows / 2,
+                col: cols - 2,
+            },
+        ];
+
+        let mut board = Board::new(rows, cols);
+        board[heads[0]] = Cell::Piece(Player { id: 1 });
+        board[heads[1]] = Cell::Piece(Player { id: 2 });
+
+        Tron {
+            board,
+            heads,
+            crashed: [false, false],
+            last_moves: None,
+        }
+    }
+
+    // where `m` would take the player, or None if it goes off the board
+    fn target(&self, m: TronMove) -> Option<Position>
+    {
+        let head = self.heads[m.player.id - 1];
+        let (d_row, d_col) = m.direction.offset();
+        let pos = (head.row as i128 + d_row, head.col as i128 + d_col);
+        match on_board(pos, (self.board.rows(), self.board.cols()))
+        {
+            true => Some(tuple_to_pos(pos)),
+            false => None,
+        }
+    }
+}
+
+impl SimultaneousGame for Tron
+{
+    type Move = TronMove;
+
+    fn get_valid_moves(&self, player: Player) -> Vec<Self::Move>
+    {
+        let moves: Vec<TronMove> = Direction::ALL
+            .iter()
+            .map(|&direction| TronMove { direction, player })
+            .collect();
+
+        // leave out moves that crash straight away, unless every move does
+        let safe: Vec<TronMove> = moves
+            .iter()
+            .copied()
+            .filter(|&m| matches!(self.target(m), Some(pos) if self.board[pos] == Cell::Empty))
+            .collect();
+        match safe.is_empty()
+        {
+            true => moves,
+            false => safe,
+        }
+    }
+
+    fn do_moves(mut self, p1_move: Self::Move, p2_move: Self::Move) -> Self
+    {
+        if p1_move.player.id != 1
+        {
+            panic!("the move: {p1_move} was not a valid move");
+        }
+        if p2_move.player.id != 2
+        {
+            panic!("the move: {p2_move} was not a valid move");
+        }
+
+        let targets = [self.target(p1_move), self.target(p2_move)];
+
+        for (i, target) in targets.iter().enumerate()
+        {
+            self.crashed[i] = match target
+            {
+                Some(pos) => self.board[*pos] != Cell::Empty || targets[1 - i] == Some(*pos),
+                None => true,
+            };
+        }
+
+        for (i, target) in targets.iter().enumerate()
+        {
+            if let (Some(pos), false) = (target, self.crashed[i])
+            {
+                self.board[*pos] = Cell::Piece(Player { id: i + 1 });
+                self.heads[i] = *pos;
+            }
+        }
+        self.last_moves = Some((p1_move, p2_move));
+
+        self
+    }
+
+    fn last_moves(&self) -> Option<(Self::Move, Self::Move)>
+    {
+        self.last_moves
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        match self.crashed
+        {
+            [true, true] => GameResult::Draw,
+            [true, false] => GameResult::Win(Player { id: 2 }),
+            [false, true] => GameResult::Win(Player { id: 1 }),
+            [false, false] => GameResult::InProgress,
+        }
+    }
+}
+
+impl Display for Tron
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        write!(f, "{}", self.board)?;
+        if self.check_win() != GameResult::InProgress
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Direction
+{
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction
+{
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    fn offset(&self) -> (i128, i128)
+    {
+        match self
+        {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+impl Display for Direction
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Direction::Up => write!(f, "Up"),
+            Direction::Down => write!(f, "Down"),
+            Direction::Left => write!(f, "Left"),
+            Direction::Right => write!(f, "Right"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TronMove
+{
+    direction: Direction,
+    player: Player,
+}
+
+impl Display for TronMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}, Direction: {}", self.player, self.direction)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{GameResult, Player, SimultaneousGame},
+        players::random::RandomPlayer,
+    };
+
+    use super::{Direction, Tron, TronMove};
+
+    fn do_moves(game: Tron, p1: Direction, p2: Direction) -> Tron
+    {
+        game.do_moves(
+            TronMove {
+                direction: p1,
+                player: Player::new(1),
+            },
+            TronMove {
+                direction: p2,
+                player: Player::new(2),
+            },
+        )
+    }
+
+    #[test]
+    fn test_head_on_collision()
+    {
+        // on a 1x5 board the players start at columns 1 and 3, and both drive into column 2
+        let game = Tron::new(1, 5);
+        assert_eq!(game.get_valid_moves(Player::new(1)).len(), 2);
+
+        let game = do_moves(game, Direction::Right, Direction::Left);
+        assert!(game.check_win() == GameResult::Draw);
+    }
+
+    #[test]
+    fn test_crash_into_wall()
+    {
+        let game = Tron::new(3, 6);
+        let game = do_moves(game, Direction::Up, Direction::Left);
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // player 2 drives into the wall player 1 left behind
+        let game = do_moves(game, Direction::Right, Direction::Left);
+        let game = do_moves(game, Direction::Right, Direction::Left);
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_board_too_small()
+    {
+        Tron::new(3, 1);
+    }
+
+    #[test]
+    fn test_random_playout()
+    {
+        let result = Tron::new(10, 10).play(
+            &mut RandomPlayer::from_seed(0),
+            &mut RandomPlayer::from_seed(1),
+            false,
+        );
+        assert!(result != GameResult::InProgress);
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::{
+    common::board::{Cell, Position},
+    tictactoe::{TicTacToe, TicTacToeMove},
+    GameResult, GameState, Player,
+};
+
+/// A 3x3 grid of tic tac toe boards. The cell played in decides which local board the opponent
+/// has to play in next, unless that board is already finished, in which case they can play in any
+/// unfinished board. Winning a local board claims the matching cell of the meta board.
+#[derive(Clone)]
+pub struct UltimateTicTacToe
+{
+    // local boards stored row by row
+    boards: Vec<TicTacToe>,
+    meta: TicTacToe,
+    next_board: Option<Position>,
+    last_move: Option<UltimateTicTacToeMove>,
+}
+
+impl UltimateTicTacToe
+{
+    pub fn new() -> UltimateTicTacToe
+    {
+        UltimateTicTacToe {
+            boards: (0..9).map(|_| TicTacToe::new(3, 3, 3)).collect(),
+            meta: TicTacToe::new(3, 3, 3),
+            next_board: None,
+            last_move: None,
+        }
+    }
+
+    fn local(&self, board: Position) -> &TicTacToe
+    {
+        &self.boards[board.row * 3 + board.col]
+    }
+
+    fn is_finished(&self, board: Position) -> bool
+    {
+        self.local(board).check_win() != GameResult::InProgress
+    }
+}
+
+impl Default for UltimateTicTacToe
+{
+    fn default() -> Self
+    {
+        UltimateTicTacToe::new()
+    }
+}
+
+impl GameState for UltimateTicTacToe
+{
+    type Move = UltimateTicTacToeMove;
+
+    fn get_valid_moves(&self) -> Vec<Self::Move>
+    {
+        if self.check_win() != GameResult::InProgress
+        {
+            return vec![];
+        }
+
+        let boards: Vec<Position> = match self.next_board
+        {
+            Some(board) if !self.is_finished(board) => vec![board],
+            // the target board is finished, so any unfinished board can be played in
+            _ => (0..3)
+                .flat_map(|row| (0..3).map(move |col| Position { row, col }))
+                .filter(|&board| !self.is_finished(board))
+                .collect(),
+        };
+
+        let player = self.player_to_move();
+        boards
+            .into_iter()
+            .flat_map(|board| {
+                self.local(board)
+                    .get_valid_moves()
+                    .into_iter()
+                    .map(move |m| UltimateTicTacToeMove {
+                        board,
+                        position: m.position,
+                        player,
+                    })
+            })
+            .collect()
+    }
+
+    fn player_to_move(&self) -> Player
+    {
+        match self.last_move
+        {
+            Some(last_move) =>
+            {
+                if last_move.player.id == 1
+                {
+                    Player { id: 2 }
+                }
+                else
+                {
+                    Player { id: 1 }
+                }
+            },
+            None => Player { id: 1 },
+        }
+    }
+
+    fn do_move(mut self, m: Self::Move) -> Self
+    {
+        // the opponent's last move decides the board, unless that board is finished
+        let board_allowed = match self.next_board
+        {
+            Some(board) if !self.is_finished(board) => m.board == board,
+            _ => m.board.row < 3 && m.board.col < 3 && !self.is_finished(m.board),
+        };
+        if m.player != self.player_to_move()
+            || !board_allowed
+            || m.position.row >= 3
+            || m.position.col >= 3
+            || self.local(m.board).board()[m.position] != Cell::Empty
+        {
+            panic!("the move: {m} was not a valid move");
+        }
+
+        let index = m.board.row * 3 + m.board.col;
+
+        // the local boards don't know about each other, so the player has to be set explicitly
+        // rather than relying on the local board's turn order
+        self.boards[index] = self.boards[index].clone().do_move(TicTacToeMove {
+            position: m.position,
+            player: m.player,
+        });
+
+        if let GameResult::Win(winner) = self.boards[index].check_win()
+        {
+            self.meta = self.meta.clone().do_move(TicTacToeMove {
+                position: m.board,
+                player: winner,
+            });
+        }
+
+        self.next_board = Some(m.position);
+        self.last_move = Some(m);
+
+        self
+    }
+
+    fn check_win(&self) -> GameResult
+    {
+        if let GameResult::Win(player) = self.meta.check_win()
+        {
+            return GameResult::Win(player);
+        }
+
+        // drawn local boards can't be claimed by anyone, so the game is a draw once every local
+        // board is finished without a line on the meta board
+        if self
+            .boards
+            .iter()
+            .all(|b| b.check_win() != GameResult::InProgress)
+        {
+            return GameResult::Draw;
+        }
+
+        GameResult::InProgress
+    }
+
+    fn last_move(&self) -> Option<Self::Move>
+    {
+        self.last_move
+    }
+}
+
+impl Display for UltimateTicTacToe
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "Board: ")?;
+        for row in 0..9
+        {
+            if row > 0 && row % 3 == 0
+            {
+                writeln!(f, "------+-------+------")?;
+            }
+            for col in 0..9
+            {
+                if col > 0 && col % 3 == 0
+                {
+                    write!(f, "| ")?;
+                }
+                let local = self.local(Position {
+                    row: row / 3,
+                    col: col / 3,
+                });
+                write!(
+                    f,
+                    "{} ",
+                    local.board()[Position {
+                        row: row % 3,
+                        col: col % 3
+                    }]
+                )?;
+            }
+            writeln!(f)?;
+        }
+        if self.check_win() == GameResult::InProgress
+        {
+            writeln!(f, "Next Player: {}", self.player_to_move())?;
+        }
+        else
+        {
+            writeln!(f, "Result: {}", self.check_win())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct UltimateTicTacToeMove
+{
+    board: Position,
+    position: Position,
+    player: Player,
+}
+
+impl Display for UltimateTicTacToeMove
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+    {
+        write!(
+            f,
+            "{}, Board: {}, Position: {}",
+            self.player, self.board, self.position
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::games::{
+        common::board::Position,
+        tictactoe::{TicTacToe, TicTacToeMove},
+        GameResult, GameState, Player,
+    };
+
+    use super::{UltimateTicTacToe, UltimateTicTacToeMove};
+
+    // a local board with the given (row, col, player) stones
+    fn local(stones: &[(usize, usize, usize)]) -> TicTacToe
+    {
+        stones
+            .iter()
+            .fold(TicTacToe::new(3, 3, 3), |board, &(row, col, id)| {
+                board.do_move(TicTacToeMove {
+                    position: Position { row, col },
+                    player: Player::new(id),
+                })
+            })
+    }
+
+    // a full local board without a line, which is a draw
+    const DRAWN: [(usize, usize, usize); 9] = [
+        (0, 0, 1),
+        (0, 1, 2),
+        (0, 2, 1),
+        (1, 0, 1),
+        (1, 1, 2),
+        (1, 2, 2),
+        (2, 0, 2),
+        (2, 1, 1),
+        (2, 2, 1),
+    ];
+
+    fn do_move(
+        game: UltimateTicTacToe,
+        board: (usize, usize),
+        position: (usize, usize),
+    ) -> UltimateTicTacToe
+    {
+        let player = game.player_to_move();
+        game.do_move(UltimateTicTacToeMove {
+            board: Position {
+                row: board.0,
+                col: board.1,
+            },
+            position: Position {
+                row: position.0,
+                col: position.1,
+            },
+            player,
+        })
+    }
+
+    #[test]
+    fn test_target_board()
+    {
+        let game = UltimateTicTacToe::new();
+        assert_eq!(game.get_valid_moves().len(), 81);
+
+        // playing the top right cell sends the opponent to the top right board
+        let game = do_move(game, (1, 1), (0, 2));
+        let moves = game.get_valid_moves();
+        assert_eq!(moves.len(), 9);
+        assert!(moves.iter().all(|m| m.board == Position { row: 0, col: 2 }));
+    }
+
+    #[test]
+    fn test_finished_target_board()
+    {
+        let mut game = UltimateTicTacToe::new();
+
+        // player 2 keeps sending player 1 back to the centre board, which player 1 wins with the top row
+        for (board, position) in [
+            ((1, 1), (0, 0)),
+            ((0, 0), (1, 1)),
+            ((1, 1), (0, 1)),
+            ((0, 1), (1, 1)),
+            ((1, 1), (0, 2)),
+        ]
+        {
+            game = do_move(game, board, position);
+        }
+
+        // the move sent player 2 to the top right board
+        game = do_move(game, (0, 2), (1, 1));
+
+        // player 1 is sent to the finished centre board, so they can play anywhere else
+        let moves = game.get_valid_moves();
+        assert_eq!(moves.len(), 81 - 9 - 3);
+        assert!(moves.iter().all(|m| m.board != Position { row: 1, col: 1 }));
+        assert!(moves.iter().all(|m| m.player == Player::new(1)));
+        assert!(game.check_win() == GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_meta_board_win()
+    {
+        // player 1 has won the top left and top middle boards, and player 2 sends them to the top
+        // right one where they have two in a row
+        let mut game = UltimateTicTacToe::new();
+        for (index, col) in [(0, 0), (1, 1)]
+        {
+            game.boards[index] = local(&[(0, 0, 1), (0, 1, 1), (0, 2, 1), (1, 0, 2), (1, 1, 2)]);
+            game.meta = game.meta.do_move(TicTacToeMove {
+                position: Position { row: 0, col },
+                player: Player::new(1),
+            });
+        }
+        game.boards[2] = local(&[(0, 0, 1), (0, 1, 1), (2, 2, 2)]);
+        game = do_move(game, (2, 2), (1, 1));
+        game = do_move(game, (1, 1), (0, 2));
+        assert!(game.player_to_move() == Player::new(1));
+        assert!(game.check_win() == GameResult::InProgress);
+
+        // finishing that board claims the third cell of the top row of the meta board
+        let game = do_move(game, (0, 2), (0, 2));
+        assert!(game.check_win() == GameResult::Win(Player::new(1)));
+        assert!(game.get_valid_moves().is_empty());
+    }
+
+    #[test]
+    fn test_every_board_finished()
+    {
+        // every board but the bottom right one is drawn, and that one has a single empty cell
+        let mut game = UltimateTicTacToe::new();
+        for index in 0..8
+        {
+            game.boards[index] = local(&DRAWN);
+        }
+        game.boards[8] = local(&DRAWN[..8]);
+        assert!(game.check_win() == GameResult::InProgress);
+
+        let game = do_move(game, (2, 2), (2, 2));
+        assert!(game.check_win() == GameResult::Draw);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_board()
+    {
+        // player 2 is sent to the top left board
+        let game = do_move(UltimateTicTacToe::new(), (1, 1), (0, 0));
+        do_move(game, (2, 2), (1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_player()
+    {
+        UltimateTicTacToe::new().do_move(UltimateTicTacToeMove {
+            board: Position { row: 1, col: 1 },
+            position: Position { row: 1, col: 1 },
+            player: Player::new(2),
+        });
+    }
+}
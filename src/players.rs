@@ -1,16 +1,24 @@
-use std::fmt::Display;
-
-use crate::games::GameState;
+use crate::games::{GameState, Player, SimultaneousGame};
 
 pub mod human;
 pub mod mcts;
 pub mod minimax;
+pub mod nested_monte_carlo;
 pub mod random;
 
-pub trait GamePlayer: Clone
+/// A player for `Game`. Most players can play any game, but being generic over the game lets a
+/// player like `MinimaxPlayer` hold parts that only work for one game, such as an evaluator.
+pub trait GamePlayer<Game>: Clone
+where
+    Game: GameState,
+{
+    fn choose_move(&mut self, game_state: &Game) -> Game::Move;
+}
+
+/// A player for games where both players move at the same time
+pub trait SimultaneousPlayer: Clone
 {
-    fn choose_move<Game>(&mut self, game_state: &Game) -> Game::Move
+    fn choose_simultaneous_move<Game>(&mut self, game_state: &Game, player: Player) -> Game::Move
     where
-        Game: GameState,
-        Game::Move: Display;
+        Game: SimultaneousGame;
 }
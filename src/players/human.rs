@@ -1,17 +1,15 @@
-use std::fmt::Display;
 use std::io::{self, Write};
 
-use crate::games::GameState;
-use crate::players::GamePlayer;
+use crate::games::{GameState, Player, SimultaneousGame};
+use crate::players::{GamePlayer, SimultaneousPlayer};
 #[derive(Clone, Copy)]
 pub struct HumanPlayer;
 
-impl GamePlayer for HumanPlayer
+impl<Game> GamePlayer<Game> for HumanPlayer
+where
+    Game: GameState,
 {
-    fn choose_move<Game>(&mut self, game_state: &Game) -> Game::Move
-    where
-        Game: GameState,
-        Game::Move: Display,
+    fn choose_move(&mut self, game_state: &Game) -> Game::Move
     {
         let moves = game_state.get_valid_moves();
         println!("{} Moves: ", moves.len());
@@ -30,6 +28,29 @@ impl GamePlayer for HumanPlayer
     }
 }
 
+impl SimultaneousPlayer for HumanPlayer
+{
+    fn choose_simultaneous_move<Game>(&mut self, game_state: &Game, player: Player) -> Game::Move
+    where
+        Game: SimultaneousGame,
+    {
+        let moves = game_state.get_valid_moves(player);
+        println!("{player}, {} Moves: ", moves.len());
+        for (i, m) in moves.iter().enumerate()
+        {
+            println!("    Move {i}: {m}");
+        }
+
+        loop
+        {
+            if let Some(index) = read_number(moves.len())
+            {
+                return moves[index];
+            }
+        }
+    }
+}
+
 fn read_number(max: usize) -> Option<usize>
 {
     print!("Enter an integer in the range [0, {}): ", max);
@@ -1,84 +1,112 @@
-use std::{
-    f64::consts::{E, SQRT_2},
-    fmt::Display,
-};
+use std::f64::consts::{E, SQRT_2};
 
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
 
-use crate::games::{GameResult, GameState};
+use crate::games::{sample_outcome, GameResult, GameState, Player};
 
 use self::arena_tree::{ArenaTree, NodeRef};
 
 use crate::players::{random::RandomPlayer, GamePlayer};
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct MCTSPlayer<T>
-where
-    T: GamePlayer,
 {
     iterations: usize,
+    // the two sides of a playout, which have their own rngs so their choices aren't correlated
     simulation_player: T,
+    simulation_opponent: T,
+    rng: StdRng,
 }
 
 impl MCTSPlayer<RandomPlayer>
 {
     pub fn new(iterations: usize) -> MCTSPlayer<RandomPlayer>
     {
+        MCTSPlayer::from_seed(iterations, thread_rng().gen())
+    }
+
+    /// Seeds both the search and the random simulation players, so that the same moves are
+    /// chosen every time
+    pub fn from_seed(iterations: usize, seed: u64) -> MCTSPlayer<RandomPlayer>
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
         MCTSPlayer {
             iterations,
-            simulation_player: RandomPlayer::new(),
+            simulation_player: RandomPlayer::from_seed(rng.gen()),
+            simulation_opponent: RandomPlayer::from_seed(rng.gen()),
+            rng,
         }
     }
 }
 
 impl<T> MCTSPlayer<T>
-where
-    T: GamePlayer,
 {
-    pub fn set_player(mut self, player: T) -> MCTSPlayer<T>
+    /// Plays both sides of every playout with copies of `player`. A random player should be given
+    /// with `set_players` instead, so that the two sides don't make the same random choices.
+    pub fn set_player<P: Clone>(self, player: P) -> MCTSPlayer<P>
     {
-        self.simulation_player = player;
-        self
+        self.set_players(player.clone(), player)
+    }
+
+    /// Sets the players for player 1 and player 2 in playouts
+    pub fn set_players<P>(self, player: P, opponent: P) -> MCTSPlayer<P>
+    {
+        MCTSPlayer {
+            iterations: self.iterations,
+            simulation_player: player,
+            simulation_opponent: opponent,
+            rng: self.rng,
+        }
     }
 }
 
-impl<T> GamePlayer for MCTSPlayer<T>
+impl<Game, T> GamePlayer<Game> for MCTSPlayer<T>
 where
-    T: GamePlayer,
+    Game: GameState,
+    T: GamePlayer<Game>,
 {
-    fn choose_move<Game>(&mut self, game_state: &Game) -> Game::Move
-    where
-        Game: GameState,
-        Game::Move: Display,
+    fn choose_move(&mut self, game_state: &Game) -> Game::Move
     {
         let mut tree = ArenaTree::new(game_state.clone(), self.iterations);
 
         for _ in 1..self.iterations
         {
-            let leaf = tree.select_leaf_node();
+            let leaf = tree.select_leaf_node(&mut self.rng);
 
-            if !tree.is_node_termnial(&leaf)
-            {
-                tree.create_children_for(&leaf);
-
-                //println!("{:?}", tree.get(&leaf));
-
-                let node_to_simulate = *tree.children_of(&leaf).choose(&mut thread_rng()).unwrap();
-
-                let mut player = self.simulation_player.clone();
-                let result = tree.simulate_node(&node_to_simulate, &mut player);
-                tree.backprop_result(&leaf, result)
-            }
-            else
+            if tree.is_node_termnial(&leaf)
             {
                 tree.backprop_result(&leaf, tree.get(&leaf).data.check_win());
+                continue;
             }
+
+            // a node is played out from itself the first time it is reached, and only expanded
+            // once it has a result of its own, so that its score isn't made of moves one level
+            // further down
+            let node_to_simulate = match tree.get(&leaf).num_plays == 0
+                && tree.get(&leaf).parent.is_some()
+            {
+                true => leaf,
+                false =>
+                {
+                    tree.create_children_for(&leaf);
+                    *tree.children_of(&leaf).choose(&mut self.rng).unwrap()
+                },
+            };
+
+            let result = tree.simulate_node(
+                &node_to_simulate,
+                (&mut self.simulation_player, &mut self.simulation_opponent),
+                &mut self.rng,
+            );
+            tree.backprop_result(&node_to_simulate, result)
         }
 
+        // the exploration term favours children that have hardly been tried, so the move played
+        // is the one the search spent the most time on
         let best_state_ref = tree
             .children_of(&tree.root_ref())
             .iter()
-            .max_by(|n1, n2| tree.get_ucb_value(n1).total_cmp(&tree.get_ucb_value(n2)))
+            .max_by_key(|n| tree.get(n).num_plays)
             .unwrap();
         let best_state = tree.get(best_state_ref).data.clone();
 
@@ -86,22 +114,29 @@ where
     }
 }
 
-trait GameStateTree
+trait GameStateTree<T>
+where
+    T: GameState,
 {
-    fn select_leaf_node(&self) -> NodeRef;
+    fn select_leaf_node(&self, rng: &mut impl Rng) -> NodeRef;
     fn create_children_for(&mut self, node: &NodeRef);
     fn is_node_termnial(&self, node: &NodeRef) -> bool;
-    fn simulate_node(&mut self, node: &NodeRef, player: &mut impl GamePlayer) -> GameResult;
+    fn simulate_node(
+        &mut self,
+        node: &NodeRef,
+        players: (&mut impl GamePlayer<T>, &mut impl GamePlayer<T>),
+        rng: &mut impl Rng,
+    ) -> GameResult;
     fn backprop_result(&mut self, node: &NodeRef, result: GameResult);
 
     fn get_ucb_value(&self, node: &NodeRef) -> f64;
 }
 
-impl<T> GameStateTree for ArenaTree<T>
+impl<T> GameStateTree<T> for ArenaTree<T>
 where
     T: GameState,
 {
-    fn select_leaf_node(&self) -> NodeRef
+    fn select_leaf_node(&self, rng: &mut impl Rng) -> NodeRef
     {
         let mut node = self.root_ref();
 
@@ -110,11 +145,16 @@ where
             // TODO: Get max child by ucb value
             // TEMP: Get random child
             //node = *self.children_of(&node).choose(&mut thread_rng()).unwrap();
-            node = *self
-                .children_of(&node)
-                .iter()
-                .max_by(|n1, n2| self.get_ucb_value(n1).total_cmp(&self.get_ucb_value(n2)))
-                .unwrap();
+            node = match self.get(&node).data.chance_outcomes()
+            {
+                // nobody chooses the outcome of a chance event, so follow one at random
+                Some(outcomes) => self.children_of(&node)[sample_outcome(&outcomes, rng)],
+                None => *self
+                    .children_of(&node)
+                    .iter()
+                    .max_by(|n1, n2| self.get_ucb_value(n1).total_cmp(&self.get_ucb_value(n2)))
+                    .unwrap(),
+            };
         }
 
         node
@@ -131,7 +171,13 @@ where
             panic!("tried to expand expanded node");
         }
 
-        let moves = self.get(node).data.get_valid_moves();
+        // the children of a chance event follow the order of its outcomes, so that the index of a
+        // sampled outcome is also the index of its child
+        let moves = match self.get(node).data.chance_outcomes()
+        {
+            Some(outcomes) => outcomes.into_iter().map(|(m, _)| m).collect(),
+            None => self.get(node).data.get_valid_moves(),
+        };
         for m in moves
         {
             let state = self.get(node).data.clone().do_move(m);
@@ -145,21 +191,47 @@ where
         self.get(node).data.check_win() != GameResult::InProgress
     }
 
-    fn simulate_node(&mut self, node: &NodeRef, player: &mut impl GamePlayer) -> GameResult
+    // chance events in the playout are sampled with the search's rng, so that a seeded search
+    // gives the same result every time
+    fn simulate_node(
+        &mut self,
+        node: &NodeRef,
+        players: (&mut impl GamePlayer<T>, &mut impl GamePlayer<T>),
+        rng: &mut impl Rng,
+    ) -> GameResult
     {
-        // TODO: allow seeding random so that mcts is deterministic for benchmarking
-        let player = RandomPlayer::new();
-        self.get(node)
-            .data
-            .clone()
-            .play(&mut player.clone(), &mut player.clone(), false)
+        let (player, opponent) = players;
+        let choose = |state: &T| match state.player_to_move() == Player::new(1)
+        {
+            true => player.choose_move(state),
+            false => opponent.choose_move(state),
+        };
+        self.get(node).data.clone().play_with(choose, rng, false)
     }
 
     fn backprop_result(&mut self, node: &NodeRef, result: GameResult)
     {
+        if let GameResult::Score(score) = result
+        {
+            let score = score as f64;
+            self.score_range = match self.score_range
+            {
+                Some((low, high)) => Some((low.min(score), high.max(score))),
+                None => Some((score, score)),
+            };
+        }
+
         let mut node = *node;
         loop
         {
+            // the score of a node is from the point of view of the player who chose to move to it,
+            // which isn't always the opposite of the player to move next
+            let mover = match self.get(&node).parent
+            {
+                Some(parent) => self.get(&parent).data.player_to_move(),
+                None => self.get(&node).data.player_to_move(),
+            };
+
             let n = self.get_mut(&node);
             n.num_plays += 1;
 
@@ -167,9 +239,10 @@ where
             {
                 GameResult::InProgress => panic!("game should be finished"),
                 GameResult::Draw => 0.5,
+                GameResult::Score(score) => *score as f64,
                 GameResult::Win(winner) =>
                 {
-                    if *winner != n.data.player_to_move()
+                    if *winner == mover
                     {
                         1.0
                     }
@@ -202,7 +275,17 @@ where
             return f64::INFINITY;
         }
 
-        let value = node.score / node.num_plays as f64;
+        // scores can be any size, so their average is scaled to 0 to 1 by the range seen so far,
+        // which keeps the exploration term balanced
+        let mut value = node.score / node.num_plays as f64;
+        if let Some((low, high)) = self.score_range
+        {
+            value = match high > low
+            {
+                true => (value - low) / (high - low),
+                false => 0.5,
+            };
+        }
         let c = SQRT_2 / 2.0;
         let exploration = ((parent.num_plays as f64).log(E) / node.num_plays as f64).sqrt();
 
@@ -220,6 +303,8 @@ mod arena_tree
     {
         nodes: ArenaVec<Node<T>>,
         root: NodeRef,
+        // the lowest and highest scores seen, for games that end with a score
+        pub score_range: Option<(f64, f64)>,
     }
     impl<T> ArenaTree<T>
     {
@@ -236,7 +321,11 @@ mod arena_tree
             let mut nodes = ArenaVec::new(capacity);
             let root = nodes.insert(node);
 
-            ArenaTree { nodes, root }
+            ArenaTree {
+                nodes,
+                root,
+                score_range: None,
+            }
         }
 
         pub fn insert(&mut self, value: T, parent: &NodeRef) -> NodeRef
@@ -299,6 +388,8 @@ mod arena_tree
         pub struct ArenaVec<T>
         {
             data: Vec<Option<T>>,
+            // nodes are never removed, so every slot before this one is full
+            next_free: usize,
         }
 
         impl<T> ArenaVec<T>
@@ -318,32 +409,19 @@ mod arena_tree
             {
                 ArenaVec {
                     data: (0..capacity).map(|_| None).collect(),
+                    next_free: 0,
                 }
             }
 
             pub fn insert(&mut self, value: T) -> NodeRef
             {
-                let mut index_to_insert = None;
-                for i in 0..self.data.len()
+                let index_to_insert = self.next_free;
+                match index_to_insert < self.data.len()
                 {
-                    if self.data[i].is_none()
-                    {
-                        index_to_insert = Some(i);
-                        break;
-                    }
+                    true => self.data[index_to_insert] = Some(value),
+                    false => self.data.push(Some(value)),
                 }
-
-                let index_to_insert = match index_to_insert
-                {
-                    Some(i) => i,
-                    None =>
-                    {
-                        self.data.push(None);
-                        self.data.len() - 1
-                    },
-                };
-
-                self.data[index_to_insert] = Some(value);
+                self.next_free += 1;
 
                 NodeRef(index_to_insert)
             }
@@ -360,3 +438,97 @@ mod arena_tree
         }
     }
 }
+
+#[cfg(test)]
+mod test
+{
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        games::{
+            backgammon::Backgammon, samegame::SameGame, tictactoe::TicTacToe, GameResult, GameState,
+        },
+        players::{random::RandomPlayer, GamePlayer},
+    };
+
+    use super::{arena_tree::ArenaTree, GameStateTree, MCTSPlayer};
+
+    #[test]
+    fn test_chance_children_follow_outcomes()
+    {
+        let game = Backgammon::new();
+        let outcomes = game.chance_outcomes().expect("the game starts with a roll");
+
+        let mut tree = ArenaTree::new(game, 100);
+        let root = tree.root_ref();
+        tree.create_children_for(&root);
+
+        let children = tree.children_of(&root);
+        assert_eq!(children.len(), outcomes.len());
+        for (child, (m, _)) in children.iter().zip(outcomes)
+        {
+            let last_move = tree.get(child).data.last_move().unwrap();
+            assert_eq!(last_move.to_string(), m.to_string());
+        }
+    }
+
+    #[test]
+    fn test_scores_are_scaled()
+    {
+        let mut tree = ArenaTree::new(SameGame::new(8, 8, 3, 0), 100);
+        let root = tree.root_ref();
+        tree.create_children_for(&root);
+        let (best, worst) = (tree.children_of(&root)[0], tree.children_of(&root)[1]);
+
+        // a clear bonus sized score would swamp the exploration term unless it is scaled
+        tree.backprop_result(&best, GameResult::Score(1000));
+        tree.backprop_result(&worst, GameResult::Score(-20));
+        let difference = tree.get_ucb_value(&best) - tree.get_ucb_value(&worst);
+        assert!((difference - 1.0).abs() < 1e-9, "{difference}");
+    }
+
+    #[test]
+    fn test_seeded_dice()
+    {
+        // playouts roll dice, which have to come from the seeded rng to give the same results
+        let playouts = || {
+            let mut tree = ArenaTree::new(Backgammon::new(), 10);
+            let root = tree.root_ref();
+            let mut players = (RandomPlayer::from_seed(0), RandomPlayer::from_seed(1));
+            let mut rng = StdRng::seed_from_u64(0);
+            (0..10)
+                .map(|_| {
+                    let players = (&mut players.0, &mut players.1);
+                    tree.simulate_node(&root, players, &mut rng).to_string()
+                })
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(playouts(), playouts());
+
+        let game = Backgammon::new();
+        let game = game.clone().do_move(game.get_valid_moves()[0]);
+        let m = MCTSPlayer::from_seed(50, 0).choose_move(&game).to_string();
+        for _ in 0..3
+        {
+            assert_eq!(
+                MCTSPlayer::from_seed(50, 0).choose_move(&game).to_string(),
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn test_playout_sides_differ()
+    {
+        // the two sides of a playout are seeded differently, so they don't copy each other
+        let mut search = MCTSPlayer::from_seed(10, 0);
+        let game = TicTacToe::new(3, 3, 3);
+        let choices = |player: &mut RandomPlayer| {
+            (0..10)
+                .map(|_| player.choose_move(&game).to_string())
+                .collect::<Vec<String>>()
+        };
+        let player = choices(&mut search.simulation_player);
+        assert_ne!(player, choices(&mut search.simulation_opponent));
+    }
+}
@@ -1,87 +1,347 @@
-use std::cmp::max_by_key;
-
 use crate::{
     games::{GameResult, GameState, Player},
     players::GamePlayer,
 };
 
+/// Estimates the value of a position that the search stops at before the game is over
+pub trait Evaluator<G>: Clone
+where
+    G: GameState,
+{
+    /// Returns the value of `state` for the player whose turn it is in `state`. Wins and losses
+    /// are infinite, so estimates should be finite.
+    fn evaluate(&self, state: &G) -> f32;
+}
+
+/// Scores every unfinished position as even, so a depth limited search only looks for wins and
+/// losses it can see within its depth
+#[derive(Clone, Copy, Default)]
+pub struct NeutralEvaluator;
+
+impl<G> Evaluator<G> for NeutralEvaluator
+where
+    G: GameState,
+{
+    fn evaluate(&self, _state: &G) -> f32
+    {
+        0.0
+    }
+}
+
+/// Counts of the work done by a search
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct SearchStats
+{
+    /// Positions searched, including the ones at the depth limit and the ends of games
+    pub nodes: usize,
+    /// Times the rest of a position's moves were skipped because it couldn't change the result
+    pub cutoffs: usize,
+}
+
 #[derive(Clone, Copy)]
-pub struct MinimaxPlayer
+pub struct MinimaxPlayer<E>
 {
     depth: Option<usize>,
+    evaluator: E,
+    pruning: bool,
+    stats: SearchStats,
+}
+
+impl MinimaxPlayer<NeutralEvaluator>
+{
+    pub fn new(depth: Option<usize>) -> MinimaxPlayer<NeutralEvaluator>
+    {
+        MinimaxPlayer {
+            depth,
+            evaluator: NeutralEvaluator,
+            pruning: true,
+            stats: SearchStats::default(),
+        }
+    }
 }
 
-impl MinimaxPlayer
+impl<E> MinimaxPlayer<E>
 {
-    pub fn new(depth: Option<usize>) -> MinimaxPlayer
+    /// Sets the evaluator used for positions at the depth limit
+    pub fn set_evaluator<F>(self, evaluator: F) -> MinimaxPlayer<F>
+    {
+        MinimaxPlayer {
+            depth: self.depth,
+            evaluator,
+            pruning: self.pruning,
+            stats: self.stats,
+        }
+    }
+
+    /// Alpha-beta pruning is on by default. Turning it off searches every position, which picks
+    /// the same moves more slowly.
+    pub fn set_pruning(mut self, pruning: bool) -> MinimaxPlayer<E>
+    {
+        self.pruning = pruning;
+        self
+    }
+
+    /// Returns the counts for the last move chosen
+    pub fn stats(&self) -> SearchStats
     {
-        MinimaxPlayer { depth }
+        self.stats
     }
 }
 
-impl GamePlayer for MinimaxPlayer
+impl<Game, E> GamePlayer<Game> for MinimaxPlayer<E>
+where
+    Game: GameState,
+    E: Evaluator<Game>,
 {
     // TODO: choose between equivalent moves by depth?
-    fn choose_move<Game>(&mut self, game_state: &Game) -> Game::Move
-    where
-        Game: crate::games::GameState,
-        Game::Move: std::fmt::Display,
+    fn choose_move(&mut self, game_state: &Game) -> Game::Move
     {
-        let mut results = vec![];
-        for m in game_state.get_valid_moves()
+        self.stats = SearchStats::default();
+        let player = game_state.player_to_move();
+        let depth = self.depth.unwrap_or(usize::MAX);
+
+        if !self.pruning
         {
+            let mut results = vec![];
+            for m in game_state.get_valid_moves()
+            {
+                let new_state = game_state.clone().do_move(m);
+                let value = value_for(player, &new_state, depth, &self.evaluator, &mut self.stats);
+                // negating a draw gives -0.0, which total_cmp ranks below 0.0, so adding 0.0
+                // turns it back into 0.0 and keeps equal values tied
+                results.push((m, value + 0.0));
+            }
+
+            return results
+                .iter()
+                .max_by(|r1, r2| r1.1.total_cmp(&r2.1))
+                .unwrap()
+                .0;
+        }
+
+        // the search above picks the last of the best moves, so the moves are searched from last
+        // to first and only replaced by a strictly better one. A move that can't beat the best so
+        // far only gets a bound back, but that bound is never more than the best.
+        let mut best: Option<(Game::Move, f32)> = None;
+        for m in game_state.get_valid_moves().into_iter().rev()
+        {
+            let alpha = best.map_or(f32::NEG_INFINITY, |(_, value)| value);
             let new_state = game_state.clone().do_move(m);
-            let value = minimax(&new_state, self.depth.unwrap_or(usize::MAX));
-            results.push((m, value));
+            let value = alpha_beta_for(
+                player,
+                &new_state,
+                depth,
+                (alpha, f32::INFINITY),
+                &self.evaluator,
+                &mut self.stats,
+            );
+            if best.is_none_or(|(_, best_value)| value > best_value)
+            {
+                best = Some((m, value));
+            }
         }
 
-        results
-            .iter()
-            .max_by(|r1, r2| r1.1.total_cmp(&r2.1))
-            .unwrap()
-            .0
+        best.unwrap().0
     }
 }
 
-fn minimax<T>(state: &T, depth: usize) -> f32
+/// Returns the value of `state` if the search stops there, either because the game is over or
+/// because the depth limit has been reached
+fn leaf_value<T, E>(state: &T, depth: usize, evaluator: &E) -> Option<f32>
 where
     T: GameState,
+    E: Evaluator<T>,
 {
-    if depth == 0
-    {
-        todo!("implement evauate function for game state");
-        // TODO:
-        // return evaluate(state);
-    }
     match state.check_win()
     {
         GameResult::InProgress =>
         {},
-        GameResult::Draw => return 0.0,
-        GameResult::Win(_) => return f32::INFINITY,
+        GameResult::Draw => return Some(0.0),
+        GameResult::Score(score) => return Some(score as f32),
+        GameResult::Win(winner) =>
+        {
+            return match winner == state.player_to_move()
+            {
+                true => Some(f32::INFINITY),
+                false => Some(f32::NEG_INFINITY),
+            }
+        },
+    }
+
+    match depth
+    {
+        0 => Some(evaluator.evaluate(state)),
+        _ => None,
+    }
+}
+
+/// Returns the value of `state` for the player whose turn it is in `state`
+fn minimax<T, E>(state: &T, depth: usize, evaluator: &E, stats: &mut SearchStats) -> f32
+where
+    T: GameState,
+    E: Evaluator<T>,
+{
+    stats.nodes += 1;
+    if let Some(value) = leaf_value(state, depth, evaluator)
+    {
+        return value;
+    }
+
+    // the value of a chance event is the expected value of its outcomes
+    if let Some(outcomes) = state.chance_outcomes()
+    {
+        return outcomes
+            .into_iter()
+            .map(|(m, p)| {
+                let new_state = state.clone().do_move(m);
+                // wins and losses are infinite, so clamp them to keep the sum from becoming NaN
+                let score = value_for(
+                    state.player_to_move(),
+                    &new_state,
+                    depth - 1,
+                    evaluator,
+                    stats,
+                );
+                p as f32 * score.clamp(f32::MIN, f32::MAX)
+            })
+            .sum();
+    }
+
+    let mut best_score = f32::NEG_INFINITY;
+    for m in state.get_valid_moves()
+    {
+        let new_state = state.clone().do_move(m);
+        let score = value_for(
+            state.player_to_move(),
+            &new_state,
+            depth - 1,
+            evaluator,
+            stats,
+        );
+        best_score = f32::max(best_score, score);
+    }
+
+    best_score
+}
+
+/// Returns the value of `state` for `player`. Games like Kalah can give a player several moves in
+/// a row, so the score is only negated when the turn actually passes to the other player.
+fn value_for<T, E>(
+    player: Player,
+    state: &T,
+    depth: usize,
+    evaluator: &E,
+    stats: &mut SearchStats,
+) -> f32
+where
+    T: GameState,
+    E: Evaluator<T>,
+{
+    let score = minimax(state, depth, evaluator, stats);
+    match state.player_to_move() == player
+    {
+        true => score,
+        false => -score,
+    }
+}
+
+/// `minimax` with fail-soft alpha-beta pruning. Only values between alpha and beta in `window`
+/// are exact. A value of at most alpha is an upper bound on the real value, and a value of at
+/// least beta is a lower bound.
+fn alpha_beta<T, E>(
+    state: &T,
+    depth: usize,
+    window: (f32, f32),
+    evaluator: &E,
+    stats: &mut SearchStats,
+) -> f32
+where
+    T: GameState,
+    E: Evaluator<T>,
+{
+    stats.nodes += 1;
+    if let Some(value) = leaf_value(state, depth, evaluator)
+    {
+        return value;
+    }
+
+    // an expected value depends on every outcome, so chance events are searched in full
+    if let Some(outcomes) = state.chance_outcomes()
+    {
+        let full = (f32::NEG_INFINITY, f32::INFINITY);
+        return outcomes
+            .into_iter()
+            .map(|(m, p)| {
+                let new_state = state.clone().do_move(m);
+                let player = state.player_to_move();
+                let score = alpha_beta_for(player, &new_state, depth - 1, full, evaluator, stats);
+                p as f32 * score.clamp(f32::MIN, f32::MAX)
+            })
+            .sum();
     }
 
+    let (mut alpha, beta) = window;
     let mut best_score = f32::NEG_INFINITY;
     for m in state.get_valid_moves()
     {
         let new_state = state.clone().do_move(m);
-        let score = minimax(&new_state, depth - 1);
+        let player = state.player_to_move();
+        let score = alpha_beta_for(
+            player,
+            &new_state,
+            depth - 1,
+            (alpha, beta),
+            evaluator,
+            stats,
+        );
         best_score = f32::max(best_score, score);
+        alpha = f32::max(alpha, score);
+        if alpha >= beta
+        {
+            stats.cutoffs += 1;
+            break;
+        }
     }
 
-    -best_score
+    best_score
+}
+
+/// `value_for` with alpha-beta pruning, where `window` is from the point of view of `player`
+fn alpha_beta_for<T, E>(
+    player: Player,
+    state: &T,
+    depth: usize,
+    window: (f32, f32),
+    evaluator: &E,
+    stats: &mut SearchStats,
+) -> f32
+where
+    T: GameState,
+    E: Evaluator<T>,
+{
+    let (alpha, beta) = window;
+    match state.player_to_move() == player
+    {
+        true => alpha_beta(state, depth, window, evaluator, stats),
+        false => -alpha_beta(state, depth, (-beta, -alpha), evaluator, stats),
+    }
 }
 
 #[cfg(test)]
 mod test
 {
-    use crate::games::{
-        common::{board::Position, create_game_tree, tree_to_file},
-        tictactoe::{TicTacToe, TicTacToeMove},
-        GameState, Player,
+    use crate::{
+        games::{
+            common::board::Position,
+            connect4::{Connect4, Connect4Evaluator},
+            kalah::Kalah,
+            tictactoe::{TicTacToe, TicTacToeEvaluator, TicTacToeMove},
+            GameResult, GameState, Player,
+        },
+        players::{random::RandomPlayer, GamePlayer},
     };
 
-    use super::minimax;
+    use super::{minimax, Evaluator, MinimaxPlayer, NeutralEvaluator, SearchStats};
 
     fn do_move(game: TicTacToe, row: usize, col: usize, player: usize) -> TicTacToe
     {
@@ -103,18 +363,128 @@ mod test
         game = do_move(game, 1, 1, 2);
         game = do_move(game, 2, 2, 1);
 
-        /*
-        game = do_move(game, 0, 2, 1);
-        game = do_move(game, 0, 0, 2);
-        game = do_move(game, 1, 0, 1);
-        game = do_move(game, 0, 1, 2);
-        game = do_move(game, 2, 0, 1);
-        game = do_move(game, 1, 2, 2);
-        */
+        // player 2 wins straight away by finishing the diagonal at (0, 2), which also blocks
+        // player 1's top row
+        assert!(minimax(&game, 1, &NeutralEvaluator, &mut SearchStats::default()) == f32::INFINITY);
+        let m = MinimaxPlayer::new(Some(1)).choose_move(&game);
+        assert!(m.position == Position { row: 0, col: 2 });
+    }
+
+    #[test]
+    fn test_depth_limit()
+    {
+        // with nothing in reach the neutral evaluator sees an even game
+        let game = TicTacToe::new(3, 3, 3);
+        assert!(minimax(&game, 0, &NeutralEvaluator, &mut SearchStats::default()) == 0.0);
+        assert!(minimax(&game, 2, &NeutralEvaluator, &mut SearchStats::default()) == 0.0);
 
-        minimax(&game, 0);
-        tree_to_file(create_game_tree(&game, None), "out\\tree.dot");
+        // the first move on an empty board is worth the most in the centre
+        let player = MinimaxPlayer::new(Some(1)).set_evaluator(TicTacToeEvaluator);
+        assert!(player.clone().choose_move(&game).position == Position { row: 1, col: 1 });
+    }
 
-        todo!()
+    #[test]
+    fn test_connect4_from_opening()
+    {
+        let mut player = MinimaxPlayer::new(Some(3)).set_evaluator(Connect4Evaluator);
+        for seed in 0..3
+        {
+            let result =
+                Connect4::new(6, 7, 4).play(&mut player, &mut RandomPlayer::from_seed(seed), false);
+            assert!(result == GameResult::Win(Player::new(1)));
+        }
+    }
+
+    #[test]
+    fn test_larger_tictactoe()
+    {
+        // four in a row on a 5x5 board is too big to search to the end
+        let mut player = MinimaxPlayer::new(Some(1)).set_evaluator(TicTacToeEvaluator);
+        for seed in 0..3
+        {
+            let result = TicTacToe::new(5, 5, 4).play(
+                &mut RandomPlayer::from_seed(seed),
+                &mut player,
+                false,
+            );
+            assert!(result == GameResult::Win(Player::new(2)));
+        }
+    }
+
+    // plays a random game, checking that both searches pick the same move at every turn
+    fn assert_same_moves<G, E>(mut game: G, depth: Option<usize>, evaluator: E, seed: u64)
+    where
+        G: GameState,
+        E: Evaluator<G>,
+    {
+        let mut pruned = MinimaxPlayer::new(depth).set_evaluator(evaluator.clone());
+        let mut exhaustive = MinimaxPlayer::new(depth)
+            .set_evaluator(evaluator)
+            .set_pruning(false);
+        let mut random = RandomPlayer::from_seed(seed);
+        while game.check_win() == GameResult::InProgress
+        {
+            let m = pruned.choose_move(&game);
+            assert_eq!(m.to_string(), exhaustive.choose_move(&game).to_string());
+            assert!(pruned.stats().nodes <= exhaustive.stats().nodes);
+            assert_eq!(exhaustive.stats().cutoffs, 0);
+
+            let m = random.choose_move(&game);
+            game = game.do_move(m);
+        }
+    }
+
+    #[test]
+    fn test_alpha_beta_benchmark_positions()
+    {
+        // the positions from the minimax benchmark, after a corner, edge or centre opening, and
+        // the replies the search chose before it had pruning
+        let game = TicTacToe::new(3, 3, 3);
+        let replies = [
+            ((0, 0), Position { row: 1, col: 1 }),
+            ((0, 1), Position { row: 2, col: 1 }),
+            ((1, 1), Position { row: 2, col: 2 }),
+        ];
+        for ((row, col), reply) in replies
+        {
+            let game = do_move(game.clone(), row, col, 1);
+            let mut pruned = MinimaxPlayer::new(None);
+            let mut exhaustive = MinimaxPlayer::new(None).set_pruning(false);
+            let m = pruned.choose_move(&game);
+            assert!(m.position == exhaustive.choose_move(&game).position);
+            assert!(m.position == reply, "{m}");
+
+            let (pruned, exhaustive) = (pruned.stats(), exhaustive.stats());
+            assert!(pruned.cutoffs > 0);
+            assert!(
+                pruned.nodes * 4 < exhaustive.nodes,
+                "{pruned:?} {exhaustive:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_alpha_beta_connect4_benchmark()
+    {
+        // the endgame from the minimax benchmark, where the search without pruning picked (3, 5)
+        let mut game = Connect4::new(6, 7, 4);
+        for i in 1..=26
+        {
+            let m = game.get_valid_moves()[if i <= 18 { 0 } else { 1 }];
+            game = game.do_move(m);
+        }
+        let m = MinimaxPlayer::new(None).choose_move(&game);
+        assert_eq!(m.to_string(), "Player 1, Position: (3, 5)");
+    }
+
+    #[test]
+    fn test_alpha_beta_matches_exhaustive()
+    {
+        for seed in 0..3
+        {
+            assert_same_moves(Connect4::new(6, 7, 4), Some(2), Connect4Evaluator, seed);
+            // Kalah gives extra turns, so the window isn't always flipped between moves
+            assert_same_moves(Kalah::new(4, 3), Some(4), NeutralEvaluator, seed);
+        }
     }
 }
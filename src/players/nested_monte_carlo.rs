@@ -0,0 +1,159 @@
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+
+use crate::{
+    games::{GameResult, GameState},
+    players::GamePlayer,
+};
+
+/// Nested Monte Carlo Search, for single player games that end with a score.
+///
+/// A level 0 search is a random playout. A search at level n tries every move with a level n - 1
+/// search after it, plays the first move of the best sequence found so far, and repeats until the
+/// game is over. Remembering the best sequence means a later step never does worse than an
+/// earlier one.
+#[derive(Clone)]
+pub struct NestedMonteCarloPlayer
+{
+    level: usize,
+    rng: StdRng,
+}
+
+impl NestedMonteCarloPlayer
+{
+    pub fn new(level: usize) -> NestedMonteCarloPlayer
+    {
+        NestedMonteCarloPlayer::from_seed(level, thread_rng().gen())
+    }
+
+    pub fn from_seed(level: usize, seed: u64) -> NestedMonteCarloPlayer
+    {
+        NestedMonteCarloPlayer {
+            level,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the best score found from `state` and the moves that reach it
+    fn search<Game>(&mut self, state: &Game, level: usize) -> (i64, Vec<Game::Move>)
+    where
+        Game: GameState,
+    {
+        if level == 0
+        {
+            return self.playout(state);
+        }
+
+        let mut state = state.clone();
+        let mut played = vec![];
+        let mut best: Option<(i64, Vec<Game::Move>)> = None;
+        while state.check_win() == GameResult::InProgress
+        {
+            for m in state.get_valid_moves()
+            {
+                let (score, mut sequence) = self.search(&state.clone().do_move(m), level - 1);
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, _)| score > *best_score)
+                {
+                    sequence.insert(0, m);
+                    best = Some((score, sequence));
+                }
+            }
+
+            // follow the best sequence, which starts from the current state
+            let (_, sequence) = best.as_mut().unwrap();
+            let m = sequence.remove(0);
+            played.push(m);
+            state = state.do_move(m);
+        }
+
+        (final_score(&state), played)
+    }
+
+    fn playout<Game>(&mut self, state: &Game) -> (i64, Vec<Game::Move>)
+    where
+        Game: GameState,
+    {
+        let mut state = state.clone();
+        let mut played = vec![];
+        while state.check_win() == GameResult::InProgress
+        {
+            let m = *state.get_valid_moves().choose(&mut self.rng).unwrap();
+            played.push(m);
+            state = state.do_move(m);
+        }
+
+        (final_score(&state), played)
+    }
+}
+
+fn final_score<Game>(state: &Game) -> i64
+where
+    Game: GameState,
+{
+    match state.check_win()
+    {
+        GameResult::Score(score) => score,
+        result =>
+        {
+            panic!("nested monte carlo search needs a game that ends with a score, not {result}")
+        },
+    }
+}
+
+impl<Game> GamePlayer<Game> for NestedMonteCarloPlayer
+where
+    Game: GameState,
+{
+    fn choose_move(&mut self, game_state: &Game) -> Game::Move
+    {
+        let (_, sequence) = self.search(game_state, self.level);
+        sequence[0]
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{
+        games::{samegame::SameGame, sliding_puzzle::SlidingPuzzle, GameResult, GameState},
+        players::random::RandomPlayer,
+    };
+
+    use super::NestedMonteCarloPlayer;
+
+    fn score(result: GameResult) -> i64
+    {
+        match result
+        {
+            GameResult::Score(score) => score,
+            _ => panic!("expected a score"),
+        }
+    }
+
+    #[test]
+    fn test_samegame()
+    {
+        let game = SameGame::new(6, 6, 3, 0);
+        let random = (0..10)
+            .map(|seed| {
+                score(
+                    game.clone()
+                        .play_alone(&mut RandomPlayer::from_seed(seed), false),
+                )
+            })
+            .max()
+            .unwrap();
+        let nested = score(game.play_alone(&mut NestedMonteCarloPlayer::from_seed(1, 0), false));
+        assert!(nested > random, "{nested} <= {random}");
+    }
+
+    #[test]
+    fn test_sliding_puzzle()
+    {
+        // a solved puzzle scores minus the number of moves, which is at most the move limit
+        let game = SlidingPuzzle::new(3).shuffle(10, 0).set_move_limit(30);
+        let result = score(game.play_alone(&mut NestedMonteCarloPlayer::from_seed(2, 0), false));
+        assert!(result >= -30, "{result}");
+    }
+}
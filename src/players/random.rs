@@ -3,7 +3,10 @@ use rand::{
     thread_rng, Rng, SeedableRng,
 };
 
-use crate::{games::GameState, players::GamePlayer};
+use crate::{
+    games::{GameState, Player, SimultaneousGame},
+    players::{GamePlayer, SimultaneousPlayer},
+};
 
 #[derive(Clone)]
 pub struct RandomPlayer
@@ -26,11 +29,11 @@ impl RandomPlayer
     }
 }
 
-impl GamePlayer for RandomPlayer
+impl<T> GamePlayer<T> for RandomPlayer
+where
+    T: GameState,
 {
-    fn choose_move<T>(&mut self, game_state: &T) -> T::Move
-    where
-        T: crate::games::GameState,
+    fn choose_move(&mut self, game_state: &T) -> T::Move
     {
         let moves = game_state.get_valid_moves();
 
@@ -39,3 +42,17 @@ impl GamePlayer for RandomPlayer
         moves[index]
     }
 }
+
+impl SimultaneousPlayer for RandomPlayer
+{
+    fn choose_simultaneous_move<T>(&mut self, game_state: &T, player: Player) -> T::Move
+    where
+        T: SimultaneousGame,
+    {
+        let moves = game_state.get_valid_moves(player);
+
+        let index = self.rng.gen_range(0..moves.len());
+
+        moves[index]
+    }
+}